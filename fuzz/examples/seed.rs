@@ -0,0 +1,19 @@
+//! Writes the examples from each puzzle description to that day's fuzzing corpus
+
+use std::fs;
+
+use advent_of_code_2022::registry;
+use advent_of_code_2022_fuzz::corpus_dir;
+
+fn main() -> std::io::Result<()> {
+    for solution in registry::solutions() {
+        let dir = corpus_dir(solution.year, solution.day);
+        fs::create_dir_all(&dir)?;
+        for (i, example) in solution.examples.iter().enumerate() {
+            let path = dir.join(format!("example-{}", i + 1));
+            fs::write(&path, example.input)?;
+            println!("wrote {}", path.display());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,5 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| advent_of_code_2022_fuzz::run(2022, 3, data));
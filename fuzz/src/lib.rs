@@ -0,0 +1,34 @@
+//! Fuzz targets for the puzzle solutions. Each day has a target that feeds arbitrary bytes to
+//! both of its parts, which may fail on input that makes no sense, but must never panic or
+//! overflow.
+//!
+//! With `cargo-fuzz` and a nightly compiler installed, run one with e.g.
+//!
+//! ```sh
+//! cargo +nightly fuzz run day5
+//! ```
+//!
+//! The corpus for each day starts from the examples in its puzzle description, which are
+//! written to `corpus/{year}/day{N}` by `cargo run --example seed`. Without a nightly compiler,
+//! `cargo test` runs the corpus and mutations of it through the same code.
+
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2022::registry;
+
+/// Runs both parts of `day` of `year` on `data`, ignoring whether they succeed
+pub fn run(year: u16, day: u16, data: &[u8]) {
+    let solution = registry::get(year, day).expect("fuzz targets are only set up for solved days");
+    let input = String::from_utf8_lossy(data);
+    for part in 1..=2 {
+        let _ = solution.run(part, &input);
+    }
+}
+
+/// The directory that holds the inputs for fuzzing `day` of `year`
+pub fn corpus_dir(year: u16, day: u16) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("corpus")
+        .join(year.to_string())
+        .join(format!("day{}", day))
+}
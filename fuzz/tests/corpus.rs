@@ -0,0 +1,86 @@
+//! Runs the fuzz targets' code over the seed corpus and simple mutations of it, so that the
+//! harness can be checked without a nightly compiler or libFuzzer
+
+use advent_of_code_2022::registry::{self, Entry};
+use advent_of_code_2022_fuzz::{corpus_dir, run};
+
+/// Bytes that are likely to upset a parser
+const INTERESTING: &[&[u8]] = &[
+    b"\n",
+    b"\r\n",
+    b"\n\n",
+    b" ",
+    b"0",
+    b"-1",
+    b"18446744073709551615",
+    b"99999999999999999999",
+    b"$ cd ..",
+    b"[Z]",
+    b"\xff",
+    "\u{e9}".as_bytes(),
+];
+
+/// A small deterministic random number generator, so that failures can be reproduced
+struct XorShift(u64);
+
+impl XorShift {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n.max(1) as u64) as usize
+    }
+}
+
+fn mutate(seed: &[u8], rng: &mut XorShift) -> Vec<u8> {
+    let mut data = seed.to_vec();
+    for _ in 0..=rng.below(4) {
+        let at = rng.below(data.len() + 1);
+        match rng.below(4) {
+            0 if at < data.len() => data[at] = rng.below(256) as u8,
+            1 => {
+                let end = (at + rng.below(16)).min(data.len());
+                data.drain(at..end);
+            }
+            2 => {
+                let insert = INTERESTING[rng.below(INTERESTING.len())];
+                data.splice(at..at, insert.iter().copied());
+            }
+            _ => data.truncate(at),
+        }
+    }
+    data
+}
+
+/// The solutions that have a corpus. A new day has none until the seed example is run.
+fn fuzzed_solutions() -> impl Iterator<Item = &'static Entry> {
+    registry::solutions()
+        .iter()
+        .filter(|solution| corpus_dir(solution.year, solution.day).is_dir())
+}
+
+#[test]
+fn test_seed_corpus_matches_the_examples() {
+    for solution in fuzzed_solutions() {
+        for (i, example) in solution.examples.iter().enumerate() {
+            let path = corpus_dir(solution.year, solution.day).join(format!("example-{}", i + 1));
+            let seed = std::fs::read_to_string(&path)
+                .unwrap_or_else(|_| panic!("{} is missing, run the seed example", path.display()));
+            assert_eq!(seed, example.input, "{}", path.display());
+        }
+    }
+}
+
+#[test]
+fn test_mutated_corpus_does_not_panic() {
+    for solution in fuzzed_solutions() {
+        let mut rng = XorShift(u64::from(solution.day).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        for entry in std::fs::read_dir(corpus_dir(solution.year, solution.day)).unwrap() {
+            let seed = std::fs::read(entry.unwrap().path()).unwrap();
+            run(solution.year, solution.day, &seed);
+            for _ in 0..500 {
+                run(solution.year, solution.day, &mutate(&seed, &mut rng));
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// The answer to a puzzle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Integer(u64),
+    Signed(i64),
+    Text(String),
+    /// An answer drawn over several lines, like the letters that some puzzles display on a
+    /// screen
+    Multiline(Vec<String>),
+}
+
+impl Answer {
+    /// Works out the type of an answer that has been written down as text, e.g. in a puzzle
+    /// description
+    pub fn infer(s: &str) -> Self {
+        if let Ok(n) = s.parse() {
+            Self::Integer(n)
+        } else if let Ok(n) = s.parse() {
+            Self::Signed(n)
+        } else {
+            s.to_owned().into()
+        }
+    }
+
+    /// The value of the answer, if it is a number
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Self::Integer(n) => Some(i128::from(*n)),
+            Self::Signed(n) => Some(i128::from(*n)),
+            _ => None,
+        }
+    }
+}
+
+/// Numbers are equal whichever integer type they were given as
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Multiline(a), Self::Multiline(b)) => a == b,
+            _ => self.as_i128().is_some() && self.as_i128() == other.as_i128(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{}", n),
+            Self::Signed(n) => write!(f, "{}", n),
+            Self::Text(s) => write!(f, "{}", s),
+            Self::Multiline(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Self::Integer(n as u64)
+            }
+        })*
+    };
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Self::Signed(n as i64)
+            }
+        })*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, usize);
+from_signed!(i8, i16, i32, i64, isize);
+
+/// Text containing line breaks becomes a [`Answer::Multiline`] answer
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        if s.contains('\n') {
+            Self::Multiline(s.lines().map(str::to_owned).collect())
+        } else {
+            Self::Text(s)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        s.to_owned().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_integers_are_equal_whatever_their_type() {
+        assert_eq!(Answer::from(24000_usize), Answer::from(24000_i64));
+        assert_ne!(Answer::from(-1), Answer::from(u64::MAX));
+        assert_ne!(Answer::from(24000_usize), Answer::from("24000"));
+    }
+
+    #[test]
+    fn test_can_infer_type_of_written_answers() {
+        assert_eq!(Answer::infer("24000"), Answer::Integer(24000));
+        assert_eq!(Answer::infer("-3"), Answer::Signed(-3));
+        assert_eq!(Answer::infer("CMZ"), Answer::Text("CMZ".into()));
+        assert_eq!(
+            Answer::infer("#..#\n####"),
+            Answer::Multiline(vec!["#..#".into(), "####".into()])
+        );
+    }
+
+    #[test]
+    fn test_serializes_as_plain_values() {
+        let answers = vec![
+            Answer::from(24000_usize),
+            Answer::from(-3),
+            Answer::from("CMZ"),
+            Answer::from("#..#\n####"),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, "[24000,-3,\"CMZ\",[\"#..#\",\"####\"]]");
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json).unwrap(), answers);
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::puzzle::Puzzle;
+
+/// The accepted answers for each puzzle, stored in a TOML file like:
+///
+/// ```toml
+/// [2022.day1]
+/// part1 = 69693
+/// part2 = 200945
+///
+/// [2022.day5]
+/// part1 = "MQTPGLLDN"
+/// ```
+#[derive(Debug)]
+pub struct Answers {
+    path: PathBuf,
+    days: BTreeMap<(u16, u16), DayAnswers>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<Answer>,
+}
+
+/// How a computed answer compares to the recorded one
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Mismatch {
+        expected: Answer,
+    },
+    /// No answer has been recorded for the puzzle yet
+    Missing,
+}
+
+impl Answers {
+    /// Loads the answers from `path`. A missing file is treated as having no answers.
+    pub fn load(path: &Path) -> Result<Self> {
+        let days = match std::fs::read_to_string(path) {
+            Ok(contents) => parse(&contents)
+                .with_context(|| format!("could not parse answers file {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("could not read answers file {}", path.display()))
+            }
+        };
+        Ok(Self {
+            path: path.to_owned(),
+            days,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::write(&self.path, serialize(&self.days)?)
+            .with_context(|| format!("could not write answers file {}", self.path.display()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, puzzle: Puzzle) -> Option<&Answer> {
+        let day = self.days.get(&(puzzle.year(), puzzle.day()))?;
+        match puzzle.part() {
+            1 => day.part1.as_ref(),
+            _ => day.part2.as_ref(),
+        }
+    }
+
+    /// Records `answer` for `puzzle`, returning the answer that it replaced, if any
+    pub fn set(&mut self, puzzle: Puzzle, answer: Answer) -> Option<Answer> {
+        let day = self.days.entry((puzzle.year(), puzzle.day())).or_default();
+        match puzzle.part() {
+            1 => day.part1.replace(answer),
+            _ => day.part2.replace(answer),
+        }
+    }
+
+    pub fn check(&self, puzzle: Puzzle, answer: &Answer) -> Check {
+        match self.get(puzzle) {
+            None => Check::Missing,
+            Some(expected) if expected == answer => Check::Pass,
+            Some(expected) => Check::Mismatch {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+fn parse(contents: &str) -> Result<BTreeMap<(u16, u16), DayAnswers>> {
+    let tables: BTreeMap<String, toml::Value> = toml::from_str(contents)?;
+    let mut days = BTreeMap::new();
+    for (name, table) in tables {
+        let year = name
+            .parse::<u16>()
+            .ok()
+            .with_context(|| format!("expected a table named {{year}}, got [{}]", name))?;
+        let by_name: BTreeMap<String, DayAnswers> = table
+            .try_into()
+            .with_context(|| format!("could not parse the answers for {}", year))?;
+        for (name, answers) in by_name {
+            days.insert((year, day_number(&name)?), answers);
+        }
+    }
+    Ok(days)
+}
+
+fn day_number(name: &str) -> Result<u16> {
+    name.strip_prefix("day")
+        .and_then(|day| day.parse::<u16>().ok())
+        .with_context(|| format!("expected a table named day{{N}}, got [{}]", name))
+}
+
+fn serialize(days: &BTreeMap<(u16, u16), DayAnswers>) -> Result<String> {
+    // Written a day at a time so that the days stay in numerical order
+    let mut contents = String::new();
+    for ((year, day), answers) in days {
+        if !contents.is_empty() {
+            contents.push('\n');
+        }
+        contents.push_str(&format!("[{}.day{}]\n", year, day));
+        contents.push_str(&toml::to_string(answers)?);
+    }
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ANSWERS: &str = "[2022.day1]
+part1 = 69693
+part2 = 200945
+
+[2022.day5]
+part1 = \"MQTPGLLDN\"
+";
+
+    fn puzzle(s: &str) -> Puzzle {
+        Puzzle::try_from(s).unwrap()
+    }
+
+    fn answers(contents: &str) -> Answers {
+        Answers {
+            path: "answers.toml".into(),
+            days: parse(contents).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_can_check_answers() {
+        let answers = answers(ANSWERS);
+        assert_eq!(answers.check(puzzle("1-2"), &200945.into()), Check::Pass);
+        assert_eq!(
+            answers.check(puzzle("1-2"), &"200945".into()),
+            Check::Mismatch {
+                expected: 200945.into()
+            }
+        );
+        assert_eq!(
+            answers.check(puzzle("5-1"), &"CMZ".into()),
+            Check::Mismatch {
+                expected: "MQTPGLLDN".into()
+            }
+        );
+        assert_eq!(answers.check(puzzle("5-2"), &"MCD".into()), Check::Missing);
+        assert_eq!(answers.check(puzzle("7-1"), &95437.into()), Check::Missing);
+    }
+
+    #[test]
+    fn test_days_are_written_in_order() {
+        let mut answers = answers(ANSWERS);
+        answers.set(puzzle("5-2"), "LVZPSTTCZ".into());
+        answers.set(puzzle("8-1"), 1672.into());
+        assert_eq!(answers.set(puzzle("1-1"), 1.into()), Some(69693.into()));
+        let expect = "[2022.day1]
+part1 = 1
+part2 = 200945
+
+[2022.day5]
+part1 = \"MQTPGLLDN\"
+part2 = \"LVZPSTTCZ\"
+
+[2022.day8]
+part1 = 1672
+";
+        assert_eq!(serialize(&answers.days).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_multiline_answers_are_written_as_arrays() {
+        let mut answers = answers("");
+        answers.set(puzzle("8-2"), "#..#\n####".into());
+        let expect = "[2022.day8]
+part2 = [\"#..#\", \"####\"]
+";
+        assert_eq!(serialize(&answers.days).unwrap(), expect);
+        assert_eq!(
+            parse(expect).unwrap()[&(2022, 8)].part2,
+            Some("#..#\n####".into())
+        );
+    }
+
+    #[test]
+    fn test_rejects_tables_that_are_not_days() {
+        assert!(parse("[puzzle1]\npart1 = \"1\"").is_err());
+        assert!(parse("[2022.puzzle1]\npart1 = \"1\"").is_err());
+        assert!(parse("[day1]\npart1 = 69693").is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::input::InputSource;
+use crate::puzzle::Puzzle;
+use crate::registry;
+
+/// Timings for repeated runs of a single puzzle
+pub struct Benchmark {
+    pub puzzle: Puzzle,
+    pub iterations: usize,
+    pub read: Stats,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats,
+}
+
+impl Benchmark {
+    /// Each phase of the benchmark, along with its name
+    pub fn phases(&self) -> [(&'static str, &Stats); 4] {
+        [
+            ("read", &self.read),
+            ("parse", &self.parse),
+            ("solve", &self.solve),
+            ("total", &self.total),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Runs `puzzle` `warmup` times without recording anything, then `iterations` times while
+/// timing reading the input, parsing it and solving the puzzle separately
+pub fn run(
+    puzzle: Puzzle,
+    source: &InputSource,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Benchmark> {
+    if iterations == 0 {
+        bail!("need to run at least one iteration");
+    }
+    let Some(solution) = registry::get(puzzle.year(), puzzle.day()) else {
+        bail!("{} day {} is not implemented", puzzle.year(), puzzle.day());
+    };
+
+    let mut read = Vec::with_capacity(iterations);
+    let mut parse = Vec::with_capacity(iterations);
+    let mut solve = Vec::with_capacity(iterations);
+    let mut total = Vec::with_capacity(iterations);
+
+    for i in 0..warmup + iterations {
+        let start = Instant::now();
+        let input = source.read(puzzle.year(), puzzle.day())?;
+        let read_done = Instant::now();
+        let parsed = solution.parse(&input)?;
+        let parse_done = Instant::now();
+        solution.solve(puzzle.part(), parsed.as_ref())?;
+        let solve_done = Instant::now();
+
+        if i >= warmup {
+            read.push(read_done - start);
+            parse.push(parse_done - read_done);
+            solve.push(solve_done - parse_done);
+            total.push(solve_done - start);
+        }
+    }
+
+    Ok(Benchmark {
+        puzzle,
+        iterations,
+        read: Stats::from_samples(&read),
+        parse: Stats::from_samples(&parse),
+        solve: Stats::from_samples(&solve),
+        total: Stats::from_samples(&total),
+    })
+}
+
+pub fn print_table(benchmarks: &[Benchmark]) {
+    print!("{}", table(benchmarks));
+}
+
+/// A table of the timings of each phase of each benchmark
+pub fn table(benchmarks: &[Benchmark]) -> String {
+    let mut table = String::new();
+    for (i, benchmark) in benchmarks.iter().enumerate() {
+        if i > 0 {
+            table.push('\n');
+        }
+        table += &format!(
+            "{} ({} iterations)\n",
+            benchmark.puzzle, benchmark.iterations
+        );
+        table += &format!(
+            "  {:<5}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+            "phase", "min", "median", "mean", "stddev"
+        );
+        for (phase, stats) in benchmark.phases() {
+            table += &format!(
+                "  {:<5}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+                phase,
+                format!("{:.3?}", stats.min),
+                format!("{:.3?}", stats.median),
+                format!("{:.3?}", stats.mean),
+                format!("{:.3?}", stats.stddev),
+            );
+        }
+    }
+    table
+}
+
+/// One phase of a benchmark, as written to CSV and JSON files
+#[derive(Serialize)]
+struct Record {
+    year: u16,
+    day: u16,
+    part: u16,
+    phase: &'static str,
+    iterations: usize,
+    min_ns: u64,
+    median_ns: u64,
+    mean_ns: u64,
+    stddev_ns: u64,
+}
+
+fn records(benchmarks: &[Benchmark]) -> Vec<Record> {
+    benchmarks
+        .iter()
+        .flat_map(|benchmark| {
+            benchmark.phases().map(|(phase, stats)| Record {
+                year: benchmark.puzzle.year(),
+                day: benchmark.puzzle.day(),
+                part: benchmark.puzzle.part(),
+                phase,
+                iterations: benchmark.iterations,
+                min_ns: stats.min.as_nanos() as u64,
+                median_ns: stats.median.as_nanos() as u64,
+                mean_ns: stats.mean.as_nanos() as u64,
+                stddev_ns: stats.stddev.as_nanos() as u64,
+            })
+        })
+        .collect()
+}
+
+/// Writes the results to `path` as either CSV or JSON, depending on the file's extension
+pub fn write(benchmarks: &[Benchmark], path: &Path) -> Result<()> {
+    let records = records(benchmarks);
+    let contents = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::to_string_pretty(&records)? + "\n",
+        Some("csv") => to_csv(&records)?,
+        _ => bail!("benchmark output file must end in either .csv or .json"),
+    };
+    std::fs::write(path, contents)
+        .with_context(|| format!("could not write benchmark results to {}", path.display()))
+}
+
+fn to_csv(records: &[Record]) -> Result<String> {
+    let mut csv = Vec::new();
+    writeln!(
+        csv,
+        "year,day,part,phase,iterations,min_ns,median_ns,mean_ns,stddev_ns"
+    )?;
+    for r in records {
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{}",
+            r.year,
+            r.day,
+            r.part,
+            r.phase,
+            r.iterations,
+            r.min_ns,
+            r.median_ns,
+            r.mean_ns,
+            r.stddev_ns
+        )?;
+    }
+    Ok(String::from_utf8(csv)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn test_stats_for_odd_number_of_samples() {
+        let stats = Stats::from_samples(&millis(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_stats_for_even_number_of_samples() {
+        let stats = Stats::from_samples(&millis(&[2, 4, 4, 4, 5, 5, 7, 9]));
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(
+            stats.median,
+            Duration::from_millis(4) + Duration::from_micros(500)
+        );
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.stddev, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_can_benchmark_a_puzzle() {
+        let source = InputSource::Text("A Y\nB X\nC Z".into());
+        let puzzle = Puzzle::try_from("2-1").unwrap();
+        let benchmark = run(puzzle, &source, 1, 3).unwrap();
+        assert_eq!(benchmark.iterations, 3);
+        assert!(benchmark.total.min >= benchmark.solve.min);
+    }
+}
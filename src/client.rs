@@ -0,0 +1,268 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use ureq::Agent;
+
+use crate::submit::Verdict;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The website asks that automated requests identify where they come from
+const USER_AGENT: &str = "github.com/petert82/advent-of-code-2022";
+
+/// Talks to the Advent of Code website, or anything else that behaves like it
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+/// Whether an input file had to be downloaded
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+impl Client {
+    /// `session` is the value of the `session` cookie set when logging in to the website
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let config = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build();
+        Self {
+            agent: Agent::new_with_config(config),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+        }
+    }
+
+    /// Downloads the puzzle input for `day` of `year`
+    pub fn input(&self, year: u16, day: u16) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call()
+            .with_context(|| format!("could not request {}", url))?;
+        let status = response.status();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("could not read response from {}", url))?;
+        match status.as_u16() {
+            200 => Ok(body),
+            400 | 401 => bail!("the session token was not accepted, it may have expired"),
+            404 => bail!("the input for {} day {} is not available yet", year, day),
+            _ => bail!("{} returned {}: {}", url, status, body.trim()),
+        }
+    }
+
+    /// Submits `answer` for a part of a puzzle, and works out from the response whether it
+    /// was right
+    pub fn submit(&self, year: u16, day: u16, part: u16, answer: &str) -> Result<Verdict> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .with_context(|| format!("could not request {}", url))?;
+        let status = response.status();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("could not read response from {}", url))?;
+        match status.as_u16() {
+            200 => Ok(Verdict::from_response(&body)),
+            400 | 401 => bail!("the session token was not accepted, it may have expired"),
+            404 => bail!("{} day {} is not available yet", year, day),
+            _ => bail!("{} returned {}: {}", url, status, body.trim()),
+        }
+    }
+
+    /// Saves the input for `day` of `year` as `{year}/day{N}.txt` in `dir`, unless it is
+    /// already there. Empty files, like the ones created for new days, are not counted as
+    /// already downloaded.
+    pub fn fetch_input(&self, year: u16, day: u16, dir: &Path) -> Result<Fetched> {
+        let dir = dir.join(year.to_string());
+        let path = dir.join(format!("day{}.txt", day));
+        if std::fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+
+        let input = self.input(year, day)?;
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("could not create {}", dir.display()))?;
+        // Write to a temporary file first so that an interrupted download is not mistaken
+        // for a complete one
+        let partial = dir.join(format!(".day{}.txt.partial", day));
+        std::fs::write(&partial, input)
+            .and_then(|_| std::fs::rename(&partial, &path))
+            .with_context(|| format!("could not write {}", path.display()))?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+/// Finds the session token in the `AOC_SESSION` environment variable, or failing that in
+/// `file`, which is [`default_session_file`] unless another is given
+pub fn session_token(file: Option<&Path>) -> Result<String> {
+    if let Ok(session) = std::env::var("AOC_SESSION") {
+        return Ok(session);
+    }
+    let Some(path) = file.map(Path::to_owned).or_else(default_session_file) else {
+        bail!("set AOC_SESSION to the session cookie from the Advent of Code website");
+    };
+    std::fs::read_to_string(&path)
+        .map(|session| session.trim().to_owned())
+        .with_context(|| {
+            format!(
+                "could not read session token from {}, set AOC_SESSION or save the session \
+                 cookie from the Advent of Code website there",
+                path.display()
+            )
+        })
+}
+
+/// The `aoc` directory in the user's config directory, where the session token and the
+/// user's settings are kept
+pub fn config_dir() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("aoc"))
+}
+
+/// The `session` file in [`config_dir`]
+pub fn default_session_file() -> Option<PathBuf> {
+    Some(config_dir()?.join("session"))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    use super::*;
+
+    /// Starts a stand-in for the website that answers a single request with `status` and
+    /// `body`. Returns the base URL to use, and a handle that gives the request it received.
+    pub(crate) fn serve_once(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_downloads_input_with_session_cookie() {
+        let (base_url, server) = serve_once(200, "1000\n2000\n");
+        let dir = temp_dir("fetch");
+        let client = Client::new(&base_url, "abc123\n");
+
+        let fetched = client.fetch_input(2022, 1, &dir).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(dir.join("2022/day1.txt")));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("2022/day1.txt")).unwrap(),
+            "1000\n2000\n"
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("session=abc123\r\n"));
+
+        // No server is listening any more, so this would fail if it tried to download again
+        assert_eq!(
+            client.fetch_input(2022, 1, &dir).unwrap(),
+            Fetched::Cached(dir.join("2022/day1.txt"))
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_empty_input_files_are_downloaded_again() {
+        let (base_url, server) = serve_once(200, "A Y\n");
+        let dir = temp_dir("fetch-empty");
+        std::fs::create_dir_all(dir.join("2023")).unwrap();
+        std::fs::write(dir.join("2023/day2.txt"), "").unwrap();
+
+        let client = Client::new(&base_url, "abc123");
+        let fetched = client.fetch_input(2023, 2, &dir).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(dir.join("2023/day2.txt")));
+        assert!(server.join().unwrap().starts_with("GET /2023/day/2/input "));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_reports_unavailable_input() {
+        let (base_url, server) = serve_once(404, "Not Found");
+        let dir = temp_dir("fetch-missing");
+        let client = Client::new(&base_url, "abc123");
+
+        let err = client.fetch_input(2022, 25, &dir).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the input for 2022 day 25 is not available yet"
+        );
+        assert!(!dir.join("2022/day25.txt").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_submits_answer_as_form() {
+        let (base_url, server) = serve_once(
+            200,
+            "<html><main><article><p>That's the right answer!</p></article></main></html>",
+        );
+        let client = Client::new(&base_url, "abc123");
+
+        assert_eq!(client.submit(2022, 4, 2, "841").unwrap(), Verdict::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/4/answer HTTP/1.1\r\n"));
+        assert!(request.contains("session=abc123\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=841"));
+    }
+}
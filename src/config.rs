@@ -0,0 +1,425 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgMatches, Command, ValueEnum};
+use serde::Deserialize;
+
+use advent_of_code_2022::client;
+
+use crate::report::{Color, Format};
+
+/// The config file for a project, looked for in the directory that the command is run from
+pub const PROJECT_FILE: &str = "aoc.toml";
+
+/// The settings in a config file, like:
+///
+/// ```toml
+/// year = 2022
+/// input_dir = "input"
+/// format = "text"
+/// color = "auto"
+///
+/// [bench]
+/// iterations = 100
+/// ```
+///
+/// Each one is the default for the command line argument of the same name, so arguments and
+/// environment variables take precedence over it. Relative paths are relative to the
+/// directory that the file is in.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Settings {
+    year: Option<u16>,
+    input_dir: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    history: Option<PathBuf>,
+    runs: Option<PathBuf>,
+    format: Option<Format>,
+    color: Option<Color>,
+    session_file: Option<PathBuf>,
+    #[serde(default)]
+    bench: BenchSettings,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BenchSettings {
+    iterations: Option<usize>,
+    warmup: Option<usize>,
+}
+
+/// A setting that is the default for a command line argument
+struct Setting {
+    /// The setting's name in config files, with dots between table names
+    key: &'static str,
+    /// The id of the argument, which is also used for it in any subcommands
+    arg: &'static str,
+    /// Whether the value is written as a number rather than a string
+    number: bool,
+}
+
+const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "year",
+        arg: "year",
+        number: true,
+    },
+    Setting {
+        key: "input_dir",
+        arg: "input_dir",
+        number: false,
+    },
+    Setting {
+        key: "answers",
+        arg: "answers",
+        number: false,
+    },
+    Setting {
+        key: "history",
+        arg: "history",
+        number: false,
+    },
+    Setting {
+        key: "runs",
+        arg: "runs",
+        number: false,
+    },
+    Setting {
+        key: "format",
+        arg: "format",
+        number: false,
+    },
+    Setting {
+        key: "color",
+        arg: "color",
+        number: false,
+    },
+    Setting {
+        key: "bench.iterations",
+        arg: "iterations",
+        number: true,
+    },
+    Setting {
+        key: "bench.warmup",
+        arg: "warmup",
+        number: true,
+    },
+];
+
+/// The name of the setting for where the session token is kept, which has no argument
+const SESSION_FILE: &str = "session_file";
+
+/// The settings from the user's config file and the project's config file, with the
+/// project's taking precedence
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The files that were looked for, and whether each one was there
+    files: Vec<(PathBuf, bool)>,
+    /// The value of each setting that a file set, along with that file
+    values: BTreeMap<&'static str, (String, PathBuf)>,
+}
+
+impl Config {
+    /// Reads `config.toml` from the user's config directory, then [`PROJECT_FILE`]. Either
+    /// can be missing.
+    pub fn load() -> Result<Self> {
+        let user = client::config_dir().map(|dir| dir.join("config.toml"));
+        let mut config = Self::default();
+        for path in user.into_iter().chain([PathBuf::from(PROJECT_FILE)]) {
+            config.read(path)?;
+        }
+        Ok(config)
+    }
+
+    /// Reads a config file, whose settings replace any that have already been read
+    fn read(&mut self, path: PathBuf) -> Result<()> {
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                self.files.push((path, false));
+                return Ok(());
+            }
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("could not read config file {}", path.display()))
+            }
+        };
+        let settings: Settings = toml::from_str(&contents)
+            .with_context(|| format!("could not parse config file {}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for (key, value) in settings.values(dir)? {
+            self.values.insert(key, (value, path.clone()));
+        }
+        self.files.push((path, true));
+        Ok(())
+    }
+
+    /// Makes the settings the defaults for the arguments of `command` and its subcommands
+    pub fn apply(&self, mut command: Command) -> Command {
+        for setting in SETTINGS {
+            if let Some((value, _)) = self.values.get(setting.key) {
+                command = set_default(command, setting.arg, value);
+            }
+        }
+        command
+    }
+
+    /// The file that the session token is read from, if one is set
+    pub fn session_file(&self) -> Option<PathBuf> {
+        self.values.get(SESSION_FILE).map(|(path, _)| path.into())
+    }
+
+    /// Prints the settings that are in effect and where each came from, as TOML that could
+    /// be used as a config file. `command` must have had the settings applied, and be what
+    /// `matches` came from.
+    pub fn show(&self, command: &Command, matches: &ArgMatches) {
+        for (path, found) in self.files.iter() {
+            let status = if *found { "read" } else { "not found" };
+            println!("# {}: {}", path.display(), status);
+        }
+        println!();
+
+        let mut lines = Vec::new();
+        for setting in SETTINGS {
+            let arg = find_arg(command, setting.arg).expect("every setting has an argument");
+            let Some((value, source)) = self.effective(arg, matches) else {
+                continue;
+            };
+            let value = if setting.number {
+                value
+            } else {
+                toml::Value::String(value).to_string()
+            };
+            lines.push((format!("{} = {}", setting.key, value), source));
+        }
+        let session_file = match self.values.get(SESSION_FILE) {
+            Some((path, file)) => Some((path.into(), file.display().to_string())),
+            None => client::default_session_file().map(|path| (path, "default".into())),
+        };
+        if let Some((path, mut source)) = session_file {
+            if std::env::var_os("AOC_SESSION").is_some() {
+                source.push_str(", not used as AOC_SESSION is set");
+            }
+            let value = toml::Value::String(path.display().to_string());
+            lines.push((format!("{} = {}", SESSION_FILE, value), source));
+        }
+
+        let width = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+        for (line, source) in lines {
+            println!("{:<width$}  # {}", line, source);
+        }
+    }
+
+    /// The value of `arg`, and where it came from
+    fn effective(&self, arg: &Arg, matches: &ArgMatches) -> Option<(String, String)> {
+        let id = arg.get_id().as_str();
+        let env = arg.get_env().map(|env| env.to_string_lossy().into_owned());
+        if let Some((value, source)) = given_value(matches, id) {
+            let source = match source {
+                ValueSource::EnvVariable => format!("environment variable {}", env?),
+                _ => "command line".into(),
+            };
+            return Some((value, source));
+        }
+        if let Some((value, file)) = self.values.get(setting_key(id)) {
+            return Some((value.clone(), file.display().to_string()));
+        }
+        if let Some(value) = env.as_ref().and_then(std::env::var_os) {
+            let source = format!("environment variable {}", env?);
+            return Some((value.to_string_lossy().into_owned(), source));
+        }
+        let default = arg.get_default_values().first()?;
+        Some((default.to_string_lossy().into_owned(), "default".into()))
+    }
+}
+
+impl Settings {
+    /// The settings that are set, as strings that can be parsed as their arguments. Paths
+    /// are made relative to `dir`.
+    fn values(self, dir: &Path) -> Result<Vec<(&'static str, String)>> {
+        let path = |path: PathBuf| -> Result<String> {
+            let path = dir.join(path);
+            path.to_str()
+                .map(str::to_owned)
+                .with_context(|| format!("{} is not valid UTF-8", path.display()))
+        };
+        let mut values = Vec::new();
+        if let Some(year) = self.year {
+            values.push(("year", year.to_string()));
+        }
+        if let Some(input_dir) = self.input_dir {
+            values.push(("input_dir", path(input_dir)?));
+        }
+        if let Some(answers) = self.answers {
+            values.push(("answers", path(answers)?));
+        }
+        if let Some(history) = self.history {
+            values.push(("history", path(history)?));
+        }
+        if let Some(runs) = self.runs {
+            values.push(("runs", path(runs)?));
+        }
+        if let Some(format) = self.format {
+            values.push(("format", value_name(format)));
+        }
+        if let Some(color) = self.color {
+            values.push(("color", value_name(color)));
+        }
+        if let Some(session_file) = self.session_file {
+            values.push((SESSION_FILE, path(session_file)?));
+        }
+        if let Some(iterations) = self.bench.iterations {
+            values.push(("bench.iterations", iterations.to_string()));
+        }
+        if let Some(warmup) = self.bench.warmup {
+            values.push(("bench.warmup", warmup.to_string()));
+        }
+        Ok(values)
+    }
+}
+
+/// The name of `value` when it is given as an argument
+fn value_name(value: impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .expect("every value can be given as an argument")
+        .get_name()
+        .to_owned()
+}
+
+/// The name in config files of the setting for the argument `id`
+fn setting_key(id: &str) -> &'static str {
+    SETTINGS
+        .iter()
+        .find(|setting| setting.arg == id)
+        .map_or("", |setting| setting.key)
+}
+
+/// Sets the default for the argument `id` wherever it appears in `command`
+fn set_default(command: Command, id: &str, value: &str) -> Command {
+    let mut command = if command.get_arguments().any(|arg| arg.get_id() == id) {
+        command.mut_arg(id, |arg| arg.default_value(value.to_owned()))
+    } else {
+        command
+    };
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_owned())
+        .collect();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |sub| set_default(sub, id, value));
+    }
+    command
+}
+
+/// Finds the argument `id` in `command` or any of its subcommands
+fn find_arg<'a>(command: &'a Command, id: &str) -> Option<&'a Arg> {
+    command
+        .get_arguments()
+        .find(|arg| arg.get_id() == id)
+        .or_else(|| command.get_subcommands().find_map(|sub| find_arg(sub, id)))
+}
+
+/// The value of `id` if it was given on the command line or in an environment variable,
+/// looking through the subcommands that were used too
+fn given_value(matches: &ArgMatches, id: &str) -> Option<(String, ValueSource)> {
+    // Checked first, as asking for the source of an argument that is not there panics
+    if let Ok(Some(mut values)) = matches.try_get_raw(id) {
+        let source = matches.value_source(id);
+        if let Some(source @ (ValueSource::CommandLine | ValueSource::EnvVariable)) = source {
+            let value = values.next()?.to_string_lossy().into_owned();
+            return Some((value, source));
+        }
+    }
+    let (_, sub) = matches.subcommand()?;
+    given_value(sub, id)
+}
+
+#[cfg(test)]
+mod test {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+    use crate::Cli;
+
+    /// Reads config files with the given names and contents, from a directory named `test`
+    fn config(test: &str, files: &[(&str, &str)]) -> Config {
+        let dir = std::env::temp_dir().join(format!("aoc-{}-{}", test, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut config = Config::default();
+        for (name, contents) in files {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+            config.read(path).unwrap();
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+        config
+    }
+
+    fn parse(config: &Config, args: &[&str]) -> (Cli, ArgMatches) {
+        let matches = config
+            .apply(Cli::command())
+            .try_get_matches_from(args)
+            .unwrap();
+        (Cli::from_arg_matches(&matches).unwrap(), matches)
+    }
+
+    #[test]
+    fn test_project_settings_take_precedence_over_user_settings() {
+        let config = config(
+            "config-precedence",
+            &[
+                ("user.toml", "year = 2021\nformat = \"json\"\n"),
+                ("aoc.toml", "year = 2022\n"),
+            ],
+        );
+        let (cli, _) = parse(&config, &["aoc", "1-1"]);
+        assert_eq!(cli.year, 2022);
+        assert_eq!(cli.format, Format::Json);
+        assert_eq!(config.values["year"].1.file_name().unwrap(), "aoc.toml");
+    }
+
+    #[test]
+    fn test_arguments_take_precedence_over_settings() {
+        let config = config(
+            "config-arguments",
+            &[("aoc.toml", "color = \"never\"\n[bench]\niterations = 5\n")],
+        );
+        let (cli, matches) = parse(&config, &["aoc", "1-1", "--color", "always"]);
+        assert_eq!(cli.color, Color::Always);
+        assert_eq!(
+            given_value(&matches, "color"),
+            Some(("always".into(), ValueSource::CommandLine))
+        );
+
+        let (cli, matches) = parse(&config, &["aoc", "bench", "1-1"]);
+        let Some(crate::Command::Bench { iterations, .. }) = cli.command else {
+            panic!("expected the bench command");
+        };
+        assert_eq!(iterations, 5);
+        assert_eq!(given_value(&matches, "iterations"), None);
+        let (_, matches) = parse(&config, &["aoc", "bench", "1-1", "-n", "7"]);
+        assert_eq!(
+            given_value(&matches, "iterations"),
+            Some(("7".into(), ValueSource::CommandLine))
+        );
+    }
+
+    #[test]
+    fn test_paths_are_relative_to_the_config_file() {
+        let config = config("config-paths", &[("aoc.toml", "input_dir = \"puzzles\"\n")]);
+        let (value, file) = &config.values["input_dir"];
+        assert_eq!(Path::new(value), file.parent().unwrap().join("puzzles"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_settings() {
+        let mut config = Config::default();
+        let path = std::env::temp_dir().join(format!("aoc-config-unknown-{}", std::process::id()));
+        std::fs::write(&path, "input_directory = \"input\"\n").unwrap();
+        assert!(config.read(path.clone()).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,614 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use advent_of_code_2022::answer::Answer;
+use advent_of_code_2022::answers::{Answers, Check};
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::parse::ParseError;
+use advent_of_code_2022::puzzle::Puzzle;
+use advent_of_code_2022::runs::{self, Run, RunLog};
+use advent_of_code_2022::{bench, registry, runner, submit};
+
+const DAYS: usize = 25;
+
+/// How far a puzzle has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    NotImplemented,
+    /// Implemented, but not run or not checked against an accepted answer
+    Implemented,
+    /// Gave the accepted answer, or the examples' answers in example mode
+    Verified,
+    /// Gave an error, or the wrong answer
+    Failing,
+}
+
+/// What is known about one part of one day
+#[derive(Debug, Clone)]
+struct PuzzleCell {
+    /// The puzzle, if it is implemented
+    puzzle: Option<Puzzle>,
+    status: Status,
+    answer: Option<Answer>,
+    /// How long solving it took, without reading and parsing the input
+    elapsed: Option<Duration>,
+}
+
+/// A grid of every puzzle in a year, which runs puzzles as they are chosen
+pub struct Dashboard {
+    year: u16,
+    source: InputSource,
+    answers: Answers,
+    /// The run log that the puzzles run on the real input are added to, unless there is none
+    runs: Option<PathBuf>,
+    warmup: usize,
+    iterations: usize,
+    /// The results on the real input, then on the examples, by day and then part
+    cells: [Vec<[PuzzleCell; 2]>; 2],
+    /// The selected day and part, counting from zero
+    selected: (usize, usize),
+    /// Whether puzzles are run on the examples from their descriptions
+    examples: bool,
+    /// What happened when something was last run
+    output: Text<'static>,
+}
+
+impl Dashboard {
+    /// Sets up the dashboard, starting with the last result of each puzzle in the run log
+    pub fn new(
+        year: u16,
+        source: InputSource,
+        answers: Answers,
+        runs: Option<PathBuf>,
+        warmup: usize,
+        iterations: usize,
+    ) -> Result<Self> {
+        let implemented: Vec<Puzzle> = Puzzle::implemented().filter(|p| p.year() == year).collect();
+        let cell = |day: usize, part: usize| {
+            let puzzle = implemented
+                .iter()
+                .find(|p| usize::from(p.day()) == day + 1 && usize::from(p.part()) == part + 1)
+                .copied();
+            PuzzleCell {
+                puzzle,
+                status: match puzzle {
+                    Some(_) => Status::Implemented,
+                    None => Status::NotImplemented,
+                },
+                answer: None,
+                elapsed: None,
+            }
+        };
+        let grid: Vec<[PuzzleCell; 2]> =
+            (0..DAYS).map(|day| [cell(day, 0), cell(day, 1)]).collect();
+        let mut last_runs = grid.clone();
+        if let Some(path) = &runs {
+            let log = RunLog::load(path)?;
+            for cell in last_runs.iter_mut().flatten() {
+                let Some((puzzle, run)) = cell
+                    .puzzle
+                    .and_then(|puzzle| Some((puzzle, log.last(puzzle)?)))
+                else {
+                    continue;
+                };
+                let answer = Answer::infer(&run.answer);
+                cell.status = match answers.check(puzzle, &answer) {
+                    Check::Pass => Status::Verified,
+                    Check::Mismatch { .. } => Status::Failing,
+                    Check::Missing => Status::Implemented,
+                };
+                cell.answer = Some(answer);
+                // The time of a run that parsed the input cannot be split up
+                cell.elapsed = (!run.parsed).then(|| run.elapsed());
+            }
+        }
+        Ok(Self {
+            year,
+            source,
+            answers,
+            runs,
+            warmup,
+            iterations,
+            cells: [last_runs, grid],
+            selected: (0, 0),
+            examples: false,
+            output: Text::from("Choose a puzzle and press Enter to run it"),
+        })
+    }
+
+    /// Takes over the terminal to show the dashboard until it is quit. The panic hook is
+    /// silenced meanwhile, as it would print over the dashboard even for the panics that
+    /// [`runner::catch_panic`] catches, which are shown in the dashboard instead.
+    pub fn show(self) -> Result<()> {
+        let mut terminal = ratatui::init();
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let res = panic::catch_unwind(AssertUnwindSafe(|| self.run(&mut terminal)));
+        panic::set_hook(hook);
+        ratatui::restore();
+        res.unwrap_or_else(|payload| {
+            bail!(
+                "the dashboard panicked: {}",
+                runner::panic_message(&*payload)
+            )
+        })
+    }
+
+    /// Draws the dashboard and handles key presses until it is quit
+    fn run(mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                return Ok(());
+            }
+            // Draw that something is running first, as it can take a while
+            if matches!(key.code, KeyCode::Enter | KeyCode::Char('r' | 'a' | 'b')) {
+                self.output = Text::from("Running...");
+                terminal.draw(|frame| self.draw(frame))?;
+            }
+            self.handle_key(key.code);
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        let (day, part) = self.selected;
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.selected.0 = day.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected.0 = (day + 1).min(DAYS - 1),
+            KeyCode::Left | KeyCode::Char('h') => self.selected.1 = 0,
+            KeyCode::Right | KeyCode::Char('l') => self.selected.1 = 1,
+            KeyCode::Tab => self.selected.1 = 1 - part,
+            KeyCode::Enter | KeyCode::Char('r') => self.output = self.run_selected(),
+            KeyCode::Char('a') => self.output = self.run_all(),
+            KeyCode::Char('b') => self.output = self.bench_selected(),
+            KeyCode::Char('e') => {
+                self.examples = !self.examples;
+                self.output = Text::from(if self.examples {
+                    "Puzzles now run on the examples from their descriptions"
+                } else {
+                    "Puzzles now run on the real input"
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn cell(&self, day: usize, part: usize) -> &PuzzleCell {
+        &self.cells[usize::from(self.examples)][day][part]
+    }
+
+    fn cell_mut(&mut self, day: usize, part: usize) -> &mut PuzzleCell {
+        &mut self.cells[usize::from(self.examples)][day][part]
+    }
+
+    fn selected_puzzle(&self) -> Result<Puzzle, Text<'static>> {
+        let (day, part) = self.selected;
+        self.cell(day, part).puzzle.ok_or_else(|| {
+            Text::from(format!(
+                "{} day {} part {} is not implemented",
+                self.year,
+                day + 1,
+                part + 1
+            ))
+        })
+    }
+
+    fn run_selected(&mut self) -> Text<'static> {
+        let puzzle = match self.selected_puzzle() {
+            Ok(puzzle) => puzzle,
+            Err(message) => return message,
+        };
+        let mut output = title(puzzle);
+        output.extend(self.run_puzzle(puzzle));
+        output
+    }
+
+    /// Runs every implemented puzzle of the year, reporting how many failed
+    fn run_all(&mut self) -> Text<'static> {
+        let puzzles: Vec<Puzzle> = self.cells[0]
+            .iter()
+            .flatten()
+            .filter_map(|cell| cell.puzzle)
+            .collect();
+        let mut failed = Vec::new();
+        for puzzle in puzzles.iter().copied() {
+            self.run_puzzle(puzzle);
+            let (day, part) = index(puzzle);
+            if self.cell(day, part).status == Status::Failing {
+                failed.push(Line::from(puzzle.to_string()).fg(Color::Red));
+            }
+        }
+        let mut output = Text::from(format!(
+            "Ran {} puzzles, {} failed",
+            puzzles.len(),
+            failed.len()
+        ));
+        output.extend(failed);
+        output
+    }
+
+    /// Runs `puzzle`, updating its cell and returning what happened
+    fn run_puzzle(&mut self, puzzle: Puzzle) -> Text<'static> {
+        let (output, cell) = if self.examples {
+            self.run_examples(puzzle)
+        } else {
+            self.run_input(puzzle)
+        };
+        let (day, part) = index(puzzle);
+        *self.cell_mut(day, part) = cell;
+        output
+    }
+
+    fn run_input(&self, puzzle: Puzzle) -> (Text<'static>, PuzzleCell) {
+        let outcome = runner::run_all(&[puzzle], &self.source, 1)
+            .0
+            .pop()
+            .expect("there is an outcome for every puzzle");
+        let mut output = Text::default();
+        if let Some(path) = &self.runs {
+            let runs = Run::from_outcomes(
+                std::slice::from_ref(&outcome),
+                runs::git_commit(),
+                submit::now(),
+            );
+            if let Err(e) = RunLog::append(path, &runs) {
+                output.extend(error_lines(&e));
+            }
+        }
+        let (status, answer) = match outcome.answer {
+            Ok(answer) => {
+                output.extend(answer_lines(&answer));
+                let status = match self.answers.check(puzzle, &answer) {
+                    Check::Pass => {
+                        output.push_line(Line::from("Matches the accepted answer").green());
+                        Status::Verified
+                    }
+                    Check::Mismatch { expected } => {
+                        let message = format!("The accepted answer is {}", expected);
+                        output.push_line(Line::from(message).red());
+                        Status::Failing
+                    }
+                    Check::Missing => {
+                        output.push_line("There is no accepted answer yet");
+                        Status::Implemented
+                    }
+                };
+                (status, Some(answer))
+            }
+            Err(e) => {
+                output.extend(error_lines(&e));
+                (Status::Failing, None)
+            }
+        };
+        let parse = outcome.parse.unwrap_or_default();
+        output.push_line(format!(
+            "Took {:.3?} to solve, after {:.3?} reading and parsing the input",
+            outcome.elapsed, parse
+        ));
+        let cell = PuzzleCell {
+            puzzle: Some(puzzle),
+            status,
+            answer,
+            elapsed: Some(outcome.elapsed),
+        };
+        (output, cell)
+    }
+
+    /// Runs `puzzle` on each of its day's examples. It is verified if every example that
+    /// has an answer for the part gives that answer.
+    fn run_examples(&self, puzzle: Puzzle) -> (Text<'static>, PuzzleCell) {
+        let solution = registry::get(puzzle.year(), puzzle.day())
+            .expect("cells only have puzzles that are implemented");
+        let mut output = Text::default();
+        let (mut status, mut last_answer) = (Status::Implemented, None);
+        let start = Instant::now();
+        for (i, example) in solution.examples.iter().enumerate() {
+            let result = runner::catch_panic(puzzle, || solution.run(puzzle.part(), example.input));
+            let label = format!("Example {}: ", i + 1);
+            let answer = match result {
+                Ok(answer) => answer,
+                Err(e) => {
+                    output.push_line(Line::from(format!("{}FAIL {:#}", label, e)).red());
+                    status = Status::Failing;
+                    continue;
+                }
+            };
+            match example.answer(puzzle.part()) {
+                Some(expected) if expected == answer => {
+                    output.push_line(Line::from(format!("{}pass {}", label, answer)).green());
+                    if status == Status::Implemented {
+                        status = Status::Verified;
+                    }
+                }
+                Some(expected) => {
+                    let message =
+                        format!("{}MISMATCH expected {}, got {}", label, expected, answer);
+                    output.push_line(Line::from(message).red());
+                    status = Status::Failing;
+                }
+                None => output.push_line(format!("{}no expected answer, got {}", label, answer)),
+            }
+            last_answer = Some(answer);
+        }
+        if solution.examples.is_empty() {
+            output.push_line("There are no examples");
+        }
+        let cell = PuzzleCell {
+            puzzle: Some(puzzle),
+            status,
+            answer: last_answer,
+            elapsed: Some(start.elapsed()),
+        };
+        (output, cell)
+    }
+
+    fn bench_selected(&mut self) -> Text<'static> {
+        let puzzle = match self.selected_puzzle() {
+            Ok(puzzle) => puzzle,
+            Err(message) => return message,
+        };
+        if self.examples {
+            return Text::from("Benchmarks use the real input, so cannot be run on examples");
+        }
+        let benchmark = runner::catch_panic(puzzle, || {
+            bench::run(puzzle, &self.source, self.warmup, self.iterations)
+        });
+        match benchmark {
+            Ok(benchmark) => Text::from(bench::table(&[benchmark])),
+            Err(e) => {
+                let mut output = title(puzzle);
+                output.extend(error_lines(&e));
+                output
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [grid, output] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(body);
+
+        let mode = if self.examples {
+            "examples"
+        } else {
+            "real input"
+        };
+        let heading = format!("Advent of Code {} (running on {})", self.year, mode);
+        frame.render_widget(Line::from(heading).bold(), header);
+
+        let rows = (0..DAYS).map(|day| {
+            Row::new([
+                Cell::from(format!("{:>3}", day + 1)),
+                self.grid_cell(day, 0),
+                self.grid_cell(day, 1),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["Day", "Part 1", "Part 2"]).bold())
+        .block(Block::bordered().title("Puzzles"))
+        .cell_highlight_style(Style::new().reversed());
+        let (day, part) = self.selected;
+        let mut state = TableState::default().with_selected_cell(Some((day, part + 1)));
+        frame.render_stateful_widget(table, grid, &mut state);
+
+        let title = match registry::get(self.year, day as u16 + 1) {
+            Some(solution) => format!("Day {}: {}", day + 1, solution.title),
+            None => format!("Day {}", day + 1),
+        };
+        let output_pane = Paragraph::new(self.output.clone())
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(output_pane, output);
+
+        let keys = "←↑↓→ choose  Enter/r run  a run all  b benchmark  e examples  q quit";
+        frame.render_widget(Line::from(keys).dim(), footer);
+    }
+
+    fn grid_cell(&self, day: usize, part: usize) -> Cell<'static> {
+        let cell = self.cell(day, part);
+        let (symbol, style) = match cell.status {
+            Status::NotImplemented => return Cell::from("  -").dim(),
+            Status::Implemented => ("○", Style::new()),
+            Status::Verified => ("✓", Style::new().green()),
+            Status::Failing => ("✗", Style::new().red()),
+        };
+        let answer = match &cell.answer {
+            Some(Answer::Multiline(lines)) => format!("({} lines)", lines.len()),
+            Some(answer) => answer.to_string(),
+            None => String::new(),
+        };
+        let elapsed = cell
+            .elapsed
+            .map(|elapsed| format!("{:.1?}", elapsed))
+            .unwrap_or_default();
+        Cell::from(format!("{} {} {}", symbol, answer, elapsed)).style(style)
+    }
+}
+
+/// The day and part of `puzzle`, counting from zero
+fn index(puzzle: Puzzle) -> (usize, usize) {
+    (
+        usize::from(puzzle.day()) - 1,
+        usize::from(puzzle.part()) - 1,
+    )
+}
+
+fn title(puzzle: Puzzle) -> Text<'static> {
+    Text::from(Line::from(puzzle.to_string()).bold())
+}
+
+fn answer_lines(answer: &Answer) -> Vec<Line<'static>> {
+    match answer {
+        Answer::Multiline(lines) => lines.iter().map(|line| Line::from(line.clone())).collect(),
+        answer => vec![Line::from(format!("Answer: {}", answer))],
+    }
+}
+
+fn error_lines(e: &anyhow::Error) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(format!("{:#}", e)).red()];
+    if let Some(parse_error) = ParseError::find(e) {
+        lines.extend(
+            parse_error
+                .snippet()
+                .lines()
+                .map(|l| Line::from(l.to_owned())),
+        );
+    }
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+
+    fn dashboard(input: &str) -> Dashboard {
+        let answers = Answers::load("no-such-answers.toml".as_ref()).unwrap();
+        Dashboard::new(2022, InputSource::Text(input.into()), answers, None, 0, 1).unwrap()
+    }
+
+    #[test]
+    fn test_cells_show_which_puzzles_are_implemented() {
+        let dashboard = dashboard("");
+        assert_eq!(dashboard.cell(0, 1).status, Status::Implemented);
+        assert_eq!(dashboard.cell(24, 0).status, Status::NotImplemented);
+    }
+
+    #[test]
+    fn test_cells_start_with_the_last_logged_runs() {
+        let path = std::env::temp_dir().join(format!("aoc-dashboard-{}.jsonl", std::process::id()));
+        let puzzles: Vec<Puzzle> = ["2-1", "2-2"]
+            .into_iter()
+            .map(|p| Puzzle::try_from(p).unwrap())
+            .collect();
+        let (outcomes, _) =
+            runner::run_all(&puzzles, &InputSource::Text("A Y\nB X\nC Z".into()), 1);
+        RunLog::append(&path, &Run::from_outcomes(&outcomes, None, 0)).unwrap();
+
+        let mut answers = Answers::load("no-such-answers.toml".as_ref()).unwrap();
+        answers.set(puzzles[0], 15.into());
+        answers.set(puzzles[1], 13.into());
+        let dashboard = Dashboard::new(
+            2022,
+            InputSource::Text(String::new()),
+            answers,
+            Some(path.clone()),
+            0,
+            1,
+        );
+        std::fs::remove_file(&path).unwrap();
+        let dashboard = dashboard.unwrap();
+
+        assert_eq!(dashboard.cell(1, 0).status, Status::Verified);
+        assert_eq!(dashboard.cell(1, 0).answer, Some(15.into()));
+        assert_eq!(dashboard.cell(1, 1).status, Status::Failing);
+        assert_eq!(
+            dashboard.cell(1, 0).elapsed,
+            None,
+            "part 1 parsed the input"
+        );
+        assert_eq!(dashboard.cell(1, 1).elapsed, Some(outcomes[1].elapsed));
+        assert_eq!(dashboard.cell(0, 0).status, Status::Implemented);
+        assert_eq!(dashboard.cell(0, 0).answer, None);
+    }
+
+    #[test]
+    fn test_running_a_puzzle_updates_its_cell() {
+        let mut dashboard = dashboard("A Y\nB X\nC Z");
+        dashboard.handle_key(KeyCode::Down);
+        dashboard.handle_key(KeyCode::Right);
+        dashboard.handle_key(KeyCode::Enter);
+        let cell = dashboard.cell(1, 1);
+        assert_eq!(cell.answer, Some(12.into()));
+        assert_eq!(
+            cell.status,
+            Status::Implemented,
+            "no answer has been accepted"
+        );
+
+        // A failure elsewhere does not change it
+        dashboard.handle_key(KeyCode::Up);
+        dashboard.handle_key(KeyCode::Enter);
+        assert_eq!(dashboard.cell(0, 1).status, Status::Failing);
+        assert_eq!(dashboard.cell(1, 1).answer, Some(12.into()));
+    }
+
+    #[test]
+    fn test_example_mode_checks_the_examples() {
+        let mut dashboard = dashboard("");
+        dashboard.handle_key(KeyCode::Char('e'));
+        dashboard.handle_key(KeyCode::Char('a'));
+        for day in 0..8 {
+            for part in 0..2 {
+                assert_eq!(dashboard.cell(day, part).status, Status::Verified);
+            }
+        }
+        dashboard.handle_key(KeyCode::Char('e'));
+        assert_eq!(dashboard.cell(0, 0).status, Status::Implemented);
+    }
+
+    #[test]
+    fn test_selection_stays_on_the_grid() {
+        let mut dashboard = dashboard("");
+        dashboard.handle_key(KeyCode::Up);
+        dashboard.handle_key(KeyCode::Left);
+        assert_eq!(dashboard.selected, (0, 0));
+        for _ in 0..30 {
+            dashboard.handle_key(KeyCode::Char('j'));
+        }
+        dashboard.handle_key(KeyCode::Tab);
+        assert_eq!(dashboard.selected, (24, 1));
+        dashboard.handle_key(KeyCode::Enter);
+        assert_eq!(
+            dashboard.output.to_string(),
+            "2022 day 25 part 2 is not implemented"
+        );
+    }
+
+    #[test]
+    fn test_draws_the_grid_and_the_selected_puzzle() {
+        let mut terminal = Terminal::new(TestBackend::new(100, 32)).unwrap();
+        let dashboard = dashboard("");
+        terminal.draw(|frame| dashboard.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Advent of Code 2022"));
+        assert!(screen.contains("Day 1: Calorie Counting"));
+        assert!(screen.contains(" 25"));
+    }
+}
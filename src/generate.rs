@@ -0,0 +1,348 @@
+use std::io::Write;
+
+use anyhow::{bail, Result};
+
+/// Writes a valid puzzle input for `day` of `year` to `out`. `size` sets how big it is, in
+/// units that suit the day, and must be at least 1. The same `seed` always gives the same
+/// input.
+pub fn generate(year: u16, day: u16, size: usize, seed: u64, out: &mut impl Write) -> Result<()> {
+    if size == 0 {
+        bail!("the size of a generated input must be at least 1");
+    }
+    let mut rng = Rng::new(seed);
+    match (year, day) {
+        (2022, 1) => calories(size, &mut rng, out),
+        (2022, 2) => rounds(size, &mut rng, out),
+        (2022, 3) => rucksacks(size, &mut rng, out),
+        (2022, 4) => assignments(size, &mut rng, out),
+        (2022, 5) => crates(size, &mut rng, out),
+        (2022, 6) => datastream(size, &mut rng, out),
+        (2022, 7) => terminal(size, &mut rng, out),
+        (2022, 8) => trees(size, &mut rng, out),
+        _ => bail!("there is no input generator for {} day {}", year, day),
+    }
+}
+
+/// A small random number generator (SplitMix64), so that inputs stay the same for a seed
+/// whatever version of any dependency is used
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `low` up to and including `high`
+    fn between(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next() % (high - low + 1) as u64) as usize
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.between(0, items.len() - 1)]
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates)
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.between(0, i));
+        }
+    }
+}
+
+fn calories(elves: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    for elf in 0..elves {
+        if elf > 0 {
+            writeln!(out)?;
+        }
+        for _ in 0..rng.between(1, 15) {
+            writeln!(out, "{}", rng.between(1000, 70000))?;
+        }
+    }
+    Ok(())
+}
+
+fn rounds(rounds: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    for _ in 0..rounds {
+        writeln!(
+            out,
+            "{} {}",
+            rng.pick(&['A', 'B', 'C']),
+            rng.pick(&['X', 'Y', 'Z'])
+        )?;
+    }
+    Ok(())
+}
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Each group shares one badge letter, and each rucksack has one letter in both
+/// compartments. Every other letter is only used in one compartment of one rucksack.
+fn rucksacks(groups: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    let mut letters = LETTERS.to_vec();
+    for _ in 0..groups {
+        rng.shuffle(&mut letters);
+        let badge = letters[0];
+        for i in 0..3 {
+            let common = letters[i + 1];
+            let own = &letters[4 + i * 16..4 + (i + 1) * 16];
+            let extra = rng.between(0, 7);
+            let mut first = vec![common, badge];
+            first.extend(&own[..extra]);
+            let mut second = vec![common];
+            second.extend(&own[8..9 + extra]);
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+            out.write_all(&first)?;
+            out.write_all(&second)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+fn assignments(pairs: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    for _ in 0..pairs {
+        let (a, c) = (rng.between(1, 99), rng.between(1, 99));
+        let (b, d) = (rng.between(a, 99), rng.between(c, 99));
+        writeln!(out, "{}-{},{}-{}", a, b, c, d)?;
+    }
+    Ok(())
+}
+
+/// Nine stacks of at least two crates, with moves that only take crates that are there and
+/// that always leave at least one crate on every stack
+fn crates(moves: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    const STACKS: usize = 9;
+    let stacks: Vec<Vec<u8>> = (0..STACKS)
+        .map(|_| {
+            (0..rng.between(2, 8))
+                .map(|_| LETTERS[rng.between(26, 51)])
+                .collect()
+        })
+        .collect();
+
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let line: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{}]", *c as char),
+                None => "   ".to_string(),
+            })
+            .collect();
+        writeln!(out, "{}", line.join(" "))?;
+    }
+    let numbers: Vec<String> = (1..=STACKS).map(|n| format!(" {} ", n)).collect();
+    writeln!(out, "{}", numbers.join(" "))?;
+    writeln!(out)?;
+
+    // Only the sizes matter for keeping the moves valid
+    let mut sizes: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for _ in 0..moves {
+        let from = loop {
+            let from = rng.between(0, STACKS - 1);
+            if sizes[from] > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.between(1, STACKS - 1)) % STACKS;
+        let num = rng.between(1, sizes[from] - 1);
+        sizes[from] -= num;
+        sizes[to] += num;
+        writeln!(out, "move {} from {} to {}", num, from + 1, to + 1)?;
+    }
+    Ok(())
+}
+
+/// Characters that never have four different ones in a row, followed by the markers for
+/// both parts, so that the whole stream has to be searched
+fn datastream(len: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    const MARKER: &[u8] = b"defghijklmnopq";
+    let filler = len.saturating_sub(MARKER.len());
+    let mut buf = Vec::with_capacity(filler.min(1 << 16));
+    for _ in 0..filler {
+        buf.push(rng.pick(b"abc"));
+        if buf.len() == buf.capacity() {
+            out.write_all(&buf)?;
+            buf.clear();
+        }
+    }
+    out.write_all(&buf)?;
+    out.write_all(MARKER)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// A directory in a generated file system
+struct Dir {
+    files: Vec<usize>,
+    dirs: Vec<usize>,
+}
+
+/// A terminal session that explores a tree of `count` directories. Directories tend to
+/// have a single subdirectory, so the tree gets deep. The files add up to between 40 and
+/// 70 million bytes, as part 2 needs.
+fn terminal(count: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    let mut dirs: Vec<Dir> = Vec::with_capacity(count);
+    let mut weights = Vec::new();
+    for i in 0..count {
+        let files = (0..rng.between(1, 4))
+            .map(|_| {
+                weights.push(rng.between(1, 1000));
+                weights.len() - 1
+            })
+            .collect();
+        dirs.push(Dir {
+            files,
+            dirs: Vec::new(),
+        });
+        if i > 0 {
+            // Usually add to one of the most recent directories
+            let parent = if rng.between(0, 3) > 0 {
+                rng.between(i.saturating_sub(3), i - 1)
+            } else {
+                rng.between(0, i - 1)
+            };
+            dirs[parent].dirs.push(i);
+        }
+    }
+
+    let total_weight: usize = weights.iter().sum();
+    let target = rng.between(45_000_000, 55_000_000);
+    let sizes: Vec<usize> = weights
+        .iter()
+        .map(|w| (w * target / total_weight).max(1))
+        .collect();
+
+    writeln!(out, "$ cd /")?;
+    // Directories are listed as they are entered. The stack holds the subdirectories that
+    // are still to be visited in each directory on the current path.
+    let mut stack = vec![list(0, &dirs, &sizes, out)?];
+    while let Some(pending) = stack.last_mut() {
+        match pending.pop() {
+            Some(dir) => {
+                writeln!(out, "$ cd {}", dir_name(dir))?;
+                let subdirs = list(dir, &dirs, &sizes, out)?;
+                stack.push(subdirs);
+            }
+            None => {
+                stack.pop();
+                if !stack.is_empty() {
+                    writeln!(out, "$ cd ..")?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the `ls` of `dir`, returning its subdirectories
+fn list(dir: usize, dirs: &[Dir], sizes: &[usize], out: &mut impl Write) -> Result<Vec<usize>> {
+    writeln!(out, "$ ls")?;
+    for sub in dirs[dir].dirs.iter() {
+        writeln!(out, "dir {}", dir_name(*sub))?;
+    }
+    for (i, file) in dirs[dir].files.iter().enumerate() {
+        writeln!(out, "{} f{}.dat", sizes[*file], i)?;
+    }
+    Ok(dirs[dir].dirs.iter().rev().copied().collect())
+}
+
+/// A unique name made of letters, as directory names cannot contain anything else
+fn dir_name(mut n: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(LETTERS[n % 26]);
+        n /= 26;
+        if n == 0 {
+            break;
+        }
+    }
+    String::from_utf8(name).expect("names are made of ASCII letters")
+}
+
+fn trees(side: usize, rng: &mut Rng, out: &mut impl Write) -> Result<()> {
+    let mut row = Vec::with_capacity(side + 1);
+    for _ in 0..side {
+        row.clear();
+        row.extend((0..side).map(|_| b'0' + rng.between(0, 9) as u8));
+        row.push(b'\n');
+        out.write_all(&row)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry;
+
+    /// The registered solutions that inputs can be generated for
+    fn generated_days() -> impl Iterator<Item = &'static registry::Entry> {
+        registry::solutions()
+            .iter()
+            .filter(|s| generate(s.year, s.day, 1, 0, &mut std::io::sink()).is_ok())
+    }
+
+    fn generated(day: u16, size: usize, seed: u64) -> String {
+        let mut out = Vec::new();
+        generate(2022, day, size, seed, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_generated_inputs_can_be_solved() {
+        for solution in generated_days() {
+            for seed in 0..20 {
+                let mut input = Vec::new();
+                generate(solution.year, solution.day, 50, seed, &mut input).unwrap();
+                let input = String::from_utf8(input).unwrap();
+                for part in 1..=2 {
+                    if let Err(e) = solution.run(part, &input) {
+                        panic!(
+                            "{} day {} part {} seed {}: {:#}",
+                            solution.year, solution.day, part, seed, e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_input() {
+        assert_eq!(generated_days().count(), 8);
+        for day in generated_days().map(|solution| solution.day) {
+            assert_eq!(generated(day, 20, 7), generated(day, 20, 7));
+            assert_ne!(generated(day, 20, 7), generated(day, 20, 8));
+        }
+        assert!(generate(2023, 1, 20, 7, &mut std::io::sink()).is_err());
+    }
+
+    #[test]
+    fn test_size_sets_how_big_the_input_is() {
+        assert_eq!(generated(6, 1000, 1).trim_end().len(), 1000);
+        assert_eq!(generated(8, 30, 1).lines().count(), 30);
+        assert_eq!(generated(2, 40, 1).lines().count(), 40);
+        assert_eq!(
+            generated(7, 25, 1).matches("$ ls").count(),
+            25,
+            "every directory is listed once"
+        );
+    }
+
+    #[test]
+    fn test_rejects_a_size_of_zero() {
+        for solution in generated_days() {
+            assert!(generate(solution.year, solution.day, 0, 1, &mut std::io::sink()).is_err());
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Where puzzle input is read from
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    /// A directory containing a `{year}/day{N}.txt` file for each day
+    Dir(PathBuf),
+    /// A single input file, used whichever puzzle is being run
+    File(PathBuf),
+    /// Input that has already been read, e.g. from stdin
+    Text(String),
+    /// Stdin, read when the puzzle runs. It can only be read once, for one puzzle.
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads all of stdin, so that it can be used as the input for a puzzle
+    pub fn from_stdin() -> Result<Self> {
+        Ok(Self::Text(Self::Stdin.read(0, 0)?))
+    }
+
+    /// The path that the input for `day` of `year` will be read from, if it is read from a
+    /// file
+    pub fn path(&self, year: u16, day: u16) -> Option<PathBuf> {
+        match self {
+            Self::Dir(dir) => Some(dir.join(year.to_string()).join(format!("day{}.txt", day))),
+            Self::File(path) => Some(path.clone()),
+            Self::Text(_) | Self::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, year: u16, day: u16) -> Result<String> {
+        match self {
+            Self::Text(input) => return Ok(input.clone()),
+            Self::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("could not read input from stdin")?;
+                return Ok(input);
+            }
+            Self::Dir(_) | Self::File(_) => {}
+        }
+        let path = self
+            .path(year, day)
+            .expect("input that is not text is read from a file");
+        std::fs::read_to_string(&path).with_context(|| self.read_error(&path, year, day))
+    }
+
+    /// Opens the input for `day` of `year`, so that it can be read as it is needed rather
+    /// than all at once
+    pub fn open(&self, year: u16, day: u16) -> Result<Box<dyn BufRead>> {
+        match self {
+            Self::Text(input) => return Ok(Box::new(Cursor::new(input.clone()))),
+            Self::Stdin => return Ok(Box::new(std::io::stdin().lock())),
+            Self::Dir(_) | Self::File(_) => {}
+        }
+        let path = self
+            .path(year, day)
+            .expect("input that is not text is read from a file");
+        let file = File::open(&path).with_context(|| self.read_error(&path, year, day))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+
+    fn read_error(&self, path: &Path, year: u16, day: u16) -> String {
+        if matches!(self, Self::Dir(_)) && !path.exists() {
+            format!(
+                "could not read input file {}, use 'fetch {} --year {}' to download it",
+                path.display(),
+                day,
+                year
+            )
+        } else {
+            format!("could not read input file {}", path.display())
+        }
+    }
+}
+
+/// A 64-bit FNV-1a hash of some input, which tells inputs apart. Unlike std's hashers, this
+/// does not change between Rust versions, so it can be saved.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dir_source_reads_file_for_day() {
+        let source = InputSource::Dir("input".into());
+        assert_eq!(
+            source.path(2022, 7),
+            Some(PathBuf::from("input/2022/day7.txt"))
+        );
+    }
+
+    #[test]
+    fn test_file_source_is_used_for_every_day() {
+        let source = InputSource::File("mine.txt".into());
+        assert_eq!(source.path(2022, 1), Some(PathBuf::from("mine.txt")));
+        assert_eq!(source.path(2023, 8), Some(PathBuf::from("mine.txt")));
+    }
+
+    #[test]
+    fn test_text_source_is_returned_as_is() {
+        let source = InputSource::Text("A Y\nB X".into());
+        assert_eq!(source.path(2022, 2), None);
+        assert_eq!(source.read(2022, 2).unwrap(), "A Y\nB X");
+    }
+
+    #[test]
+    fn test_hash_is_fnv_1a() {
+        assert_eq!(hash(""), 0xcbf29ce484222325);
+        assert_eq!(hash("a"), 0xaf63dc4c8601ec8c);
+    }
+}
@@ -0,0 +1,20 @@
+//! Solutions to the [Advent of Code](https://adventofcode.com) puzzles, one module per year.
+//!
+//! Each day's module, such as [`y2022::day7`], exposes `part1` and `part2` functions along
+//! with the types and parsers used for that day's input. [`registry`] lists every solution,
+//! and [`puzzle::Puzzle`] runs them against input files.
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod generate;
+pub mod input;
+pub mod parse;
+pub mod puzzle;
+pub mod registry;
+pub mod runner;
+pub mod runs;
+pub mod solution;
+pub mod submit;
+pub mod y2022;
@@ -1,34 +1,735 @@
-use anyhow::Result;
-use clap::Parser;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod puzzle;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use advent_of_code_2022::answers::{Answers, Check};
+use advent_of_code_2022::client::{self, Client, Fetched};
+use advent_of_code_2022::input::{self, InputSource};
+use advent_of_code_2022::parse::ParseError;
+use advent_of_code_2022::puzzle::{self, Puzzle, Selection};
+use advent_of_code_2022::runs::{self, Run, RunLog};
+use advent_of_code_2022::submit::{self, Attempt, History, Verdict};
+use advent_of_code_2022::{bench, generate, registry, runner};
+use anyhow::{bail, Context, Result};
+use clap::error::ErrorKind;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+
+mod config;
+mod dashboard;
+mod report;
+mod scaffold;
+mod watch;
+
+use config::Config;
+use dashboard::Dashboard;
+use report::Palette;
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    /// e.g. '1-2' to run the solution for day 1, part 2
-    #[arg(value_parser = str_to_puzzle)]
-    puzzle: puzzle::Puzzle,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// e.g. '1-2' to run the solution for day 1, part 2. Use 'all', a range of days like
+    /// '1..=8', or '3-*' for all parts of day 3 to run several puzzles at once. Start with a
+    /// year, as in '2022-1-2' or '2022-all', for puzzles from a year other than --year.
+    #[arg(required = true)]
+    puzzle: Option<String>,
+
+    /// The year of the puzzles that are given without one
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_YEAR",
+        default_value_t = puzzle::DEFAULT_YEAR,
+        value_parser = clap::value_parser!(u16).range(2015..)
+    )]
+    year: u16,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t = report::Format::Text)]
+    format: report::Format,
+
+    /// When to color the results
+    #[arg(long, value_enum, default_value_t = report::Color::Auto)]
+    color: report::Color,
+
+    /// Number of puzzles to run at the same time
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: u16,
+
+    /// Run the puzzles on the examples from their descriptions instead of the real input
+    #[arg(long, conflicts_with_all = ["input", "check", "record"])]
+    example: bool,
+
+    /// Read the input as the puzzle runs rather than all at once, so that inputs too large
+    /// for memory can be used. Only some days can do this.
+    #[arg(long, conflicts_with_all = ["example", "watch", "check", "record", "format", "jobs"])]
+    stream: bool,
+
+    /// Keep running, and run the puzzles again whenever their input files change
+    #[arg(long, conflicts_with_all = ["example", "check", "record"])]
+    watch: bool,
+
+    /// Compare the answers with the accepted answers in the answers file
+    #[arg(long, conflicts_with = "record")]
+    check: bool,
+
+    /// Save the answers to the answers file as the accepted answers
+    #[arg(long)]
+    record: bool,
+
+    /// File containing the accepted answer to each puzzle
+    #[arg(long, value_name = "PATH", default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// File that the answer and runtime of every puzzle that is run are added to
+    #[arg(long, global = true, value_name = "PATH", default_value = "runs.jsonl")]
+    runs: PathBuf,
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Read the puzzle input from this file instead of the input directory. Use '-' to read
+    /// it from stdin.
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Directory containing an input file named '{year}/day{N}.txt' for each day
+    #[arg(
+        long,
+        value_name = "DIR",
+        env = "AOC_INPUT_DIR",
+        default_value = "input"
+    )]
+    input_dir: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the puzzles that have been implemented
+    List,
+    /// Time how long puzzles take to run
+    Bench {
+        /// The puzzle to benchmark, or several puzzles in the same format as when running them
+        puzzle: String,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Number of timed runs of each puzzle
+        #[arg(short = 'n', long, default_value_t = 100)]
+        iterations: usize,
+
+        /// Number of runs of each puzzle before timing starts
+        #[arg(long, default_value_t = 10)]
+        warmup: usize,
+
+        /// Also write the results to this file, as either .csv or .json
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Show every puzzle of the year in a terminal UI, and run them from there
+    Dashboard {
+        /// Directory containing an input file named '{year}/day{N}.txt' for each day
+        #[arg(
+            long,
+            value_name = "DIR",
+            env = "AOC_INPUT_DIR",
+            default_value = "input"
+        )]
+        input_dir: PathBuf,
+
+        /// Number of timed runs when benchmarking a puzzle
+        #[arg(short = 'n', long, default_value_t = 100)]
+        iterations: usize,
+
+        /// Number of runs before timing starts when benchmarking a puzzle
+        #[arg(long, default_value_t = 10)]
+        warmup: usize,
+    },
+    /// Show how the answers and runtimes of puzzles have changed over the runs in the run
+    /// log
+    History {
+        /// The puzzles to show, in the same format as when running them
+        #[arg(default_value = "all")]
+        puzzle: String,
+
+        /// Flag puzzles whose runs at the latest commit took this many percent longer than
+        /// at the commit before
+        #[arg(long, value_name = "PERCENT", default_value_t = 20.0)]
+        threshold: f64,
+    },
+    /// Download the puzzle input for a day, unless it has already been downloaded
+    Fetch {
+        /// The day to download the input for
+        #[arg(value_parser = clap::value_parser!(u16).range(1..=25))]
+        day: u16,
+
+        /// Directory to save the input file in, under a directory for the year
+        #[arg(
+            long,
+            value_name = "DIR",
+            env = "AOC_INPUT_DIR",
+            default_value = "input"
+        )]
+        input_dir: PathBuf,
+
+        /// Address of the Advent of Code website
+        #[arg(long, value_name = "URL", env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+    },
+    /// Submit the answer to a puzzle to the website, unless it is already known to be wrong
+    Submit {
+        /// The puzzle to submit the answer to, e.g. '1-2' or '2022-1-2'
+        puzzle: String,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Address of the Advent of Code website
+        #[arg(long, value_name = "URL", env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+
+        /// File recording every answer that has been submitted
+        #[arg(long, value_name = "PATH", default_value = "submissions.jsonl")]
+        history: PathBuf,
+    },
+    /// Write a large, valid input for a day, for benchmarking and profiling against
+    Generate {
+        /// The day to generate input for
+        #[arg(value_parser = clap::value_parser!(u16).range(1..=25))]
+        day: u16,
+
+        /// How big the input is: the number of elves (day 1), rounds (2), groups of rucksacks
+        /// (3), pairs (4), moves (5), characters (6) or directories (7), or the width and
+        /// height of the grid (8)
+        #[arg(long)]
+        size: usize,
+
+        /// The same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Write the input to this file rather than to standard output
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Work with the settings, which can be given in a project's aoc.toml or in config.toml
+    /// in the user's aoc config directory
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Add a module for a new day, ready for solving it
+    New {
+        /// The day to add
+        day: u16,
+
+        /// The root directory of this project
+        #[arg(long, value_name = "DIR", default_value = env!("CARGO_MANIFEST_DIR"))]
+        root: PathBuf,
+    },
 }
 
-fn str_to_puzzle(s: &str) -> Result<puzzle::Puzzle, String> {
-    puzzle::Puzzle::try_from(s)
+/// Parses the puzzles given on the command line, which must include at least one that is
+/// implemented. This cannot be left to clap, as puzzles without a year are from --year,
+/// which clap may not have parsed yet.
+fn selection(s: &str, year: u16) -> Selection {
+    let selection = Selection::parse_in(s, year).unwrap_or_else(|e| invalid_puzzle(s, e));
+    if selection.puzzles().is_empty() {
+        invalid_puzzle(
+            s,
+            "none of its puzzles are implemented, use the 'list' command to see which are".into(),
+        );
+    }
+    selection
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn single_puzzle(s: &str, year: u16) -> Puzzle {
+    Puzzle::parse_in(s, year).unwrap_or_else(|e| invalid_puzzle(s, e))
+}
 
-    println!("Running {}", cli.puzzle);
+/// Exits, reporting an invalid puzzle the same way as clap reports other invalid arguments
+fn invalid_puzzle(s: &str, e: String) -> ! {
+    Cli::command()
+        .error(
+            ErrorKind::ValueValidation,
+            format!("invalid value '{}' for '<PUZZLE>': {}", s, e),
+        )
+        .exit()
+}
 
-    let result = cli.puzzle.run()?;
-    println!("{}", result);
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the settings that are in effect, and where each one came from
+    Show,
+}
+
+fn main() -> ExitCode {
+    match load_and_run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            if let Some(parse_error) = ParseError::find(&e) {
+                eprintln!();
+                eprintln!("{}", parse_error.snippet());
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the arguments, using the settings from the config files as their defaults
+fn load_and_run() -> Result<()> {
+    let config = Config::load()?;
+    let command = config.apply(Cli::command());
+    let matches = command.clone().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    run(cli, &config, &command, &matches)
+}
+
+fn run(cli: Cli, config: &Config, command: &clap::Command, matches: &ArgMatches) -> Result<()> {
+    let palette = Palette::new(cli.color);
+    match cli.command {
+        Some(Command::List) => {
+            for solution in registry::solutions() {
+                println!(
+                    "{} day {:>2}: {}",
+                    solution.year, solution.day, solution.title
+                );
+            }
+            return Ok(());
+        }
+        Some(Command::Bench {
+            puzzle,
+            input,
+            iterations,
+            warmup,
+            output,
+        }) => {
+            let puzzle = selection(&puzzle, cli.year);
+            let source = input_source(&input, &puzzle)?;
+            let benchmarks = puzzle
+                .puzzles()
+                .into_iter()
+                .map(|p| bench::run(p, &source, warmup, iterations))
+                .collect::<Result<Vec<_>>>()?;
+            bench::print_table(&benchmarks);
+            if let Some(output) = output {
+                bench::write(&benchmarks, &output)?;
+            }
+            return Ok(());
+        }
+        Some(Command::Dashboard {
+            input_dir,
+            iterations,
+            warmup,
+        }) => {
+            let answers = Answers::load(&cli.answers)?;
+            let source = InputSource::Dir(input_dir);
+            let dashboard = Dashboard::new(
+                cli.year,
+                source,
+                answers,
+                Some(cli.runs),
+                warmup,
+                iterations,
+            )?;
+            return dashboard.show();
+        }
+        Some(Command::History { puzzle, threshold }) => {
+            let selection = selection(&puzzle, cli.year);
+            let log = RunLog::load(&cli.runs)?;
+            report::print_history(&selection.puzzles(), &log, threshold, palette);
+            return Ok(());
+        }
+        Some(Command::Fetch {
+            day,
+            input_dir,
+            base_url,
+        }) => {
+            let session = client::session_token(config.session_file().as_deref())?;
+            let client = Client::new(&base_url, &session);
+            match client.fetch_input(cli.year, day, &input_dir)? {
+                Fetched::Cached(path) => println!("{} has already been downloaded", path.display()),
+                Fetched::Downloaded(path) => println!("downloaded {}", path.display()),
+            }
+            return Ok(());
+        }
+        Some(Command::Submit {
+            puzzle,
+            input,
+            base_url,
+            history,
+        }) => {
+            let puzzle = single_puzzle(&puzzle, cli.year);
+            let session_file = config.session_file();
+            return submit_answer(puzzle, &input, &base_url, &history, session_file.as_deref());
+        }
+        Some(Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        }) => {
+            return generate_input(cli.year, day, size, seed, output.as_deref());
+        }
+        Some(Command::Config {
+            action: ConfigAction::Show,
+        }) => {
+            config.show(command, matches);
+            return Ok(());
+        }
+        Some(Command::New { day, root }) => {
+            let scaffolded = scaffold::new_day(&root, cli.year, day)?;
+            for path in scaffolded.created {
+                println!("created {}", path.display());
+            }
+            for path in scaffolded.updated {
+                println!("updated {}", path.display());
+            }
+            for path in scaffolded.skipped {
+                println!("left existing {} alone", path.display());
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let selection = selection(
+        cli.puzzle
+            .as_deref()
+            .expect("clap requires a puzzle when there is no subcommand"),
+        cli.year,
+    );
+    if cli.example {
+        return run_examples(&selection, palette);
+    }
+    if cli.stream {
+        return run_streaming(&selection, &cli.input, &cli.runs, palette);
+    }
+    let source = input_source(&cli.input, &selection)?;
+    let jobs = usize::from(cli.jobs);
+
+    if cli.check || cli.record {
+        let mut answers = Answers::load(&cli.answers)?;
+        let (outcomes, _) = runner::run_all(&selection.puzzles(), &source, jobs);
+        let res = if cli.check {
+            check_answers(&outcomes, &answers, palette)
+        } else {
+            record_answers(&outcomes, &mut answers, palette)
+        };
+        log_outcomes(&outcomes, &cli.runs, palette);
+        return res;
+    }
+
+    if cli.watch {
+        let puzzles = selection.puzzles();
+        let mut paths = Vec::new();
+        for puzzle in puzzles.iter() {
+            let Some(path) = source.path(puzzle.year(), puzzle.day()) else {
+                bail!("--watch cannot be used when reading input from stdin");
+            };
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        watch::watch(&paths, || {
+            watch::clear_screen();
+            for path in paths.iter() {
+                println!("Watching {}", path.display());
+            }
+            println!();
+            let (outcomes, _) = runner::run_all(&puzzles, &source, jobs);
+            report::print(&outcomes, cli.format, palette);
+            log_outcomes(&outcomes, &cli.runs, palette);
+        });
+    }
+
+    if let (Selection::Single(puzzle), report::Format::Text) = (&selection, cli.format) {
+        println!("Running {}", puzzle);
+
+        let start = Instant::now();
+        let text = source.read(puzzle.year(), puzzle.day())?;
+        let input = puzzle.parse_input(&text, &source)?;
+        let parse = start.elapsed();
+        let start = Instant::now();
+        let answer = puzzle.solve(input.as_ref())?;
+        let elapsed = start.elapsed();
+        println!("{}", answer);
+
+        let outcome = runner::Outcome {
+            puzzle: *puzzle,
+            answer: Ok(answer),
+            elapsed,
+            parse: Some(parse),
+            input_hash: Some(input::hash(&text)),
+        };
+        log_outcomes(&[outcome], &cli.runs, palette);
+        return Ok(());
+    }
+
+    let start = Instant::now();
+    let (outcomes, threads) = runner::run_all(&selection.puzzles(), &source, jobs);
+    let wall = start.elapsed();
+    report::print(&outcomes, cli.format, palette);
+    if cli.format == report::Format::Text {
+        report::print_timing(&outcomes, wall, threads);
+    }
+    log_outcomes(&outcomes, &cli.runs, palette);
+
+    let failed = outcomes.iter().filter(|o| o.answer.is_err()).count();
+    if failed > 0 {
+        bail!("{} of {} puzzles failed", failed, outcomes.len());
+    }
 
     Ok(())
 }
+
+/// Adds the puzzles that gave an answer to the run log
+fn log_outcomes(outcomes: &[runner::Outcome], path: &Path, palette: Palette) {
+    let commit = runs::git_commit();
+    let runs = Run::from_outcomes(outcomes, commit, submit::now());
+    log_runs(&runs, path, palette);
+}
+
+/// Adds runs to the run log. Only warns if it cannot, as the puzzles have already been run
+/// and their results reported.
+fn log_runs(runs: &[Run], path: &Path, palette: Palette) {
+    if let Err(e) = RunLog::append(path, runs) {
+        eprintln!(
+            "{}: the runs were not logged: {:#}",
+            palette.bad("Warning"),
+            e
+        );
+    }
+}
+
+fn run_streaming(
+    selection: &Selection,
+    input: &InputArgs,
+    runs: &Path,
+    palette: Palette,
+) -> Result<()> {
+    let Selection::Single(puzzle) = selection else {
+        bail!("--stream can only be used when running a single puzzle");
+    };
+    let source = match &input.input {
+        Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+        _ => input_source(input, selection)?,
+    };
+    println!("Running {}", puzzle);
+    let start = Instant::now();
+    let answer = puzzle.run_streaming(&source)?;
+    let elapsed = start.elapsed();
+    println!("{}", answer);
+
+    let commit = runs::git_commit();
+    let run = Run::streamed(*puzzle, &answer, elapsed, commit, submit::now());
+    log_runs(&[run], runs, palette);
+    Ok(())
+}
+
+fn check_answers(outcomes: &[runner::Outcome], answers: &Answers, palette: Palette) -> Result<()> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for outcome in outcomes {
+        let puzzle = outcome.puzzle;
+        let answer = match &outcome.answer {
+            Ok(answer) => answer,
+            Err(e) => {
+                failed += 1;
+                println!("{}: {} {:#}", puzzle, palette.bad("FAIL"), e);
+                continue;
+            }
+        };
+        match answers.check(puzzle, answer) {
+            Check::Pass => {
+                passed += 1;
+                println!("{}: {} {}", puzzle, palette.good("pass"), answer);
+            }
+            Check::Mismatch { expected } => {
+                failed += 1;
+                println!(
+                    "{}: {} expected {}, got {}",
+                    puzzle,
+                    palette.bad("MISMATCH"),
+                    expected,
+                    answer
+                );
+            }
+            Check::Missing => {
+                missing += 1;
+                println!("{}: no accepted answer, got {}", puzzle, answer);
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        bail!(
+            "{} of {} puzzles did not give the accepted answer",
+            failed,
+            outcomes.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_examples(selection: &Selection, palette: Palette) -> Result<()> {
+    let (mut passed, mut failed) = (0, 0);
+    for puzzle in selection.puzzles() {
+        let Some(solution) = registry::get(puzzle.year(), puzzle.day()) else {
+            bail!("{} day {} is not implemented", puzzle.year(), puzzle.day());
+        };
+        if solution.examples.is_empty() {
+            println!("{}: no examples", puzzle);
+        }
+        for (i, example) in solution.examples.iter().enumerate() {
+            let label = format!("{} example {}", puzzle, i + 1);
+            let answer = match solution.run(puzzle.part(), example.input) {
+                Ok(answer) => answer,
+                Err(e) => {
+                    failed += 1;
+                    println!("{}: {} {:#}", label, palette.bad("FAIL"), e);
+                    continue;
+                }
+            };
+            match example.answer(puzzle.part()) {
+                Some(expected) if expected == answer => {
+                    passed += 1;
+                    println!("{}: {} {}", label, palette.good("pass"), answer);
+                }
+                Some(expected) => {
+                    failed += 1;
+                    println!(
+                        "{}: {} expected {}, got {}",
+                        label,
+                        palette.bad("MISMATCH"),
+                        expected,
+                        answer
+                    );
+                }
+                None => println!("{}: no expected answer, got {}", label, answer),
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed", passed, failed);
+    if failed > 0 {
+        bail!("{} examples did not give the expected answer", failed);
+    }
+    Ok(())
+}
+
+fn record_answers(
+    outcomes: &[runner::Outcome],
+    answers: &mut Answers,
+    palette: Palette,
+) -> Result<()> {
+    let mut failed = 0;
+    for outcome in outcomes {
+        let puzzle = outcome.puzzle;
+        match &outcome.answer {
+            Ok(answer) => match answers.set(puzzle, answer.clone()) {
+                Some(previous) if previous == *answer => {
+                    println!("{}: unchanged {}", puzzle, answer)
+                }
+                Some(previous) => println!("{}: recorded {} (was {})", puzzle, answer, previous),
+                None => println!("{}: recorded {}", puzzle, answer),
+            },
+            Err(e) => {
+                failed += 1;
+                println!("{}: {} {:#}", puzzle, palette.bad("FAIL"), e);
+            }
+        }
+    }
+    answers.save()?;
+    println!();
+    println!("Saved answers to {}", answers.path().display());
+
+    if failed > 0 {
+        bail!("{} of {} puzzles failed", failed, outcomes.len());
+    }
+    Ok(())
+}
+
+fn submit_answer(
+    puzzle: Puzzle,
+    input: &InputArgs,
+    base_url: &str,
+    history: &Path,
+    session_file: Option<&Path>,
+) -> Result<()> {
+    let source = input_source(input, &Selection::Single(puzzle))?;
+    let answer = puzzle.run(&source)?.to_string();
+    let mut history = History::load(history)?;
+    let submitted_at = submit::now();
+    history.check_can_submit(puzzle, &answer, submitted_at)?;
+
+    println!("Submitting {} for {}", answer, puzzle);
+    let client = Client::new(base_url, &client::session_token(session_file)?);
+    let verdict = client.submit(puzzle.year(), puzzle.day(), puzzle.part(), &answer)?;
+    println!("{}", verdict);
+    let correct = verdict == Verdict::Correct;
+    history.record(Attempt {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        part: puzzle.part(),
+        answer: answer.clone(),
+        submitted_at,
+        verdict,
+    })?;
+
+    if !correct {
+        bail!("{} was not accepted", answer);
+    }
+    Ok(())
+}
+
+fn generate_input(
+    year: u16,
+    day: u16,
+    size: usize,
+    seed: u64,
+    output: Option<&Path>,
+) -> Result<()> {
+    match output {
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("could not create {}", path.display()))?;
+            let mut out = BufWriter::new(file);
+            generate::generate(year, day, size, seed, &mut out)?;
+            out.flush()?;
+        }
+        None => {
+            let mut out = BufWriter::new(io::stdout().lock());
+            generate::generate(year, day, size, seed, &mut out)?;
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn input_source(args: &InputArgs, selection: &Selection) -> Result<InputSource> {
+    let Some(input) = &args.input else {
+        return Ok(InputSource::Dir(args.input_dir.clone()));
+    };
+    let puzzles = selection.puzzles();
+    let day = |p: &Puzzle| (p.year(), p.day());
+    if puzzles.iter().any(|p| day(p) != day(&puzzles[0])) {
+        bail!("--input can only be used when running puzzles from a single day");
+    }
+    if input.as_os_str() == "-" {
+        InputSource::from_stdin()
+    } else {
+        Ok(InputSource::File(input.to_owned()))
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt::Display;
+use std::io::BufRead;
+
+use anyhow::{Context, Error};
+use nom::IResult;
+
+/// A problem with puzzle input, pointing at where in the input it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line that the problem was found on, starting from 1
+    pub line: usize,
+    /// The character on the line that the problem starts at, starting from 1
+    pub column: usize,
+    /// How many characters the problem covers
+    pub width: usize,
+    /// The whole of the offending line
+    pub text: String,
+    /// A description of what should have been there
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error `offset` bytes into `input`, covering the word or number found there
+    pub fn at(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let text = input[line_start..line_end].trim_end_matches('\r');
+        let before = &input[line_start..offset];
+        let token = input[offset..line_end]
+            .split(|c: char| !c.is_alphanumeric())
+            .next()
+            .unwrap_or_default();
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: before.chars().count() + 1,
+            width: token.chars().count().max(1),
+            text: text.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error about the whole of line `line` (counting from 1) of `input`
+    pub fn line(input: &str, line: usize, expected: impl Into<String>) -> Self {
+        let text = input.lines().nth(line - 1).unwrap_or_default();
+        Self {
+            line,
+            column: 1,
+            width: text.chars().count().max(1),
+            text: text.to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Moves the error down by `lines`, for errors in part of a larger input
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Finds the parse error that caused `err`, if there is one
+    pub fn find(err: &Error) -> Option<&Self> {
+        err.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+
+    /// The offending line with the problem underlined, e.g.
+    ///
+    /// ```text
+    ///   |
+    /// 2 | 2-x,4-5
+    ///   |   ^ expected a range like '2-4'
+    /// ```
+    pub fn snippet(&self) -> String {
+        let gutter = self.line.to_string().len();
+        format!(
+            "{:gutter$} |\n{} | {}\n{:gutter$} | {:indent$}{} expected {}",
+            "",
+            self.line,
+            self.text,
+            "",
+            "",
+            "^".repeat(self.width),
+            self.expected,
+            indent = self.column - 1,
+        )
+    }
+
+    fn found(&self) -> String {
+        let rest: String = self.text.chars().skip(self.column - 1).collect();
+        if rest.is_empty() {
+            "the end of the line".to_owned()
+        } else {
+            format!("'{}'", rest)
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line,
+            self.column,
+            self.expected,
+            self.found()
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line of `input` with `parser`, which has to use up the whole line. `expected`
+/// describes what a line should look like, for the error when one does not.
+pub fn lines<'a, T>(
+    input: &'a str,
+    expected: &str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<Vec<T>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::at(input, 0, expected));
+    }
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, content)| line(content, i + 1, expected, &mut parser))
+        .collect()
+}
+
+/// Parses a single line of a larger input with `parser`, which has to use up the whole line.
+/// `number` is the line's number, counting from 1, so that errors point at the right line.
+pub fn line<'a, T>(
+    content: &'a str,
+    number: usize,
+    expected: &str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T, ParseError> {
+    let content = content.trim_end_matches(['\n', '\r']);
+    let error = match parser(content) {
+        Ok(("", value)) => return Ok(value),
+        Ok((rest, _)) => error_at(content, rest, expected),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => error_at(content, e.input, expected),
+        Err(nom::Err::Incomplete(_)) => ParseError::at(content, content.len(), expected),
+    };
+    Err(error.offset_lines(number - 1))
+}
+
+/// Calls `f` with each line read from `reader` and the line's number, counting from 1,
+/// without holding more than one line in memory. As with [`lines`], input that is all
+/// whitespace is an error, and `expected` describes what a line should look like.
+pub fn each_line(
+    mut reader: impl BufRead,
+    expected: &str,
+    mut f: impl FnMut(&str, usize) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut content = String::new();
+    let mut number = 0;
+    let mut blank = true;
+    loop {
+        content.clear();
+        number += 1;
+        let read = reader
+            .read_line(&mut content)
+            .with_context(|| format!("could not read line {} of the input", number))?;
+        if read == 0 {
+            break;
+        }
+        blank = blank && content.trim().is_empty();
+        f(content.trim_end_matches(['\n', '\r']), number)?;
+    }
+    if blank {
+        return Err(ParseError::at("", 0, expected).into());
+    }
+    Ok(())
+}
+
+/// The characters read from `reader` one at a time, for input that is not split into lines
+pub fn chars(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<char>> {
+    let mut bytes = reader.bytes();
+    std::iter::from_fn(move || {
+        let first = match bytes.next()? {
+            Ok(byte) => byte,
+            Err(e) => return Some(Err(Error::new(e).context("could not read the input"))),
+        };
+        // The first byte of a UTF-8 character gives how many bytes it takes up
+        let len = match first {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => 0,
+        };
+        let mut buf = [first, 0, 0, 0];
+        for byte in buf.iter_mut().take(len).skip(1) {
+            match bytes.next() {
+                Some(Ok(next)) => *byte = next,
+                Some(Err(e)) => {
+                    return Some(Err(Error::new(e).context("could not read the input")))
+                }
+                None => break,
+            }
+        }
+        Some(
+            std::str::from_utf8(&buf[..len])
+                .ok()
+                .and_then(|c| c.chars().next())
+                .context("the input is not valid UTF-8"),
+        )
+    })
+}
+
+/// The error for a parser that stopped at `rest`, which is the end of `line`
+fn error_at(line: &str, rest: &str, expected: &str) -> ParseError {
+    ParseError::at(line, line.len() - rest.len(), expected)
+}
+
+/// Gets what a nom parser had left to parse when it failed
+pub fn remaining<'a>(err: &nom::Err<nom::error::Error<&'a str>>) -> Option<&'a str> {
+    match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => Some(e.input),
+        nom::Err::Incomplete(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nom::character::complete::digit1;
+
+    use super::*;
+
+    #[test]
+    fn test_points_at_the_offending_character() {
+        let err = ParseError::at("2-4,6-8\n2-x,4-5\n", 10, "a number");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.text, "2-x,4-5");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected a number, found 'x,4-5'"
+        );
+        assert_eq!(err.snippet(), "  |\n2 | 2-x,4-5\n  |   ^ expected a number");
+    }
+
+    #[test]
+    fn test_reports_end_of_truncated_line() {
+        let err = ParseError::at("1000\n20", 7, "a blank line");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected a blank line, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn test_lines_reports_the_first_bad_line() {
+        let err = lines("12\n34\n5a6\n", "a number", digit1).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(
+            lines("12\r\n34\n", "a number", digit1),
+            Ok(vec!["12", "34"])
+        );
+        assert_eq!(lines("", "a number", digit1).unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn test_each_line_numbers_lines_like_lines_does() {
+        let mut parsed = Vec::new();
+        each_line("12\r\n34\n".as_bytes(), "a number", |content, number| {
+            parsed.push((
+                line(content, number, "a number", digit1)?.to_owned(),
+                number,
+            ));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(parsed, vec![("12".to_owned(), 1), ("34".to_owned(), 2)]);
+
+        let err = each_line("12\n34\n5a6\n".as_bytes(), "a number", |content, number| {
+            line(content, number, "a number", digit1)?;
+            Ok(())
+        })
+        .unwrap_err();
+        let err = ParseError::find(&err).unwrap();
+        assert_eq!(
+            err,
+            &lines("12\n34\n5a6\n", "a number", digit1).unwrap_err()
+        );
+        assert!(each_line(" \n\n".as_bytes(), "a number", |_, _| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_chars_decodes_utf8() {
+        let read = |bytes: &[u8]| chars(bytes).collect::<anyhow::Result<String>>();
+        assert_eq!(read("a✓b€\n".as_bytes()).unwrap(), "a✓b€\n");
+        assert!(read(&[b'a', 0xff]).is_err());
+        assert!(read(&[b'a', 0xe2, 0x9c]).is_err());
+    }
+}
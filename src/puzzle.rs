@@ -1,66 +1,324 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, Result};
+use std::any::Any;
 use std::fmt::Display;
 
-#[derive(Debug, Clone)]
+use crate::answer::Answer;
+use crate::input::InputSource;
+use crate::parse::ParseError;
+use crate::registry;
+
+/// The year of the puzzles that are given without one, unless another is chosen
+pub const DEFAULT_YEAR: u16 = 2022;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Puzzle {
+    year: u16,
     day: u16,
     part: u16,
 }
 
 impl Puzzle {
-    pub fn run(&self) -> Result<Box<dyn Display>> {
-        let input_file = format!("./input/day{}.txt", self.day);
-        let input = std::fs::read_to_string(input_file).context("could not read input file")?;
-        match (self.day, self.part) {
-            (1, 1) => Ok(Box::new(crate::day1::part1(input.as_ref())?)),
-            (1, 2) => Ok(Box::new(crate::day1::part2(input.as_ref())?)),
-            (2, 1) => Ok(Box::new(crate::day2::part1(input.as_ref())?)),
-            (2, 2) => Ok(Box::new(crate::day2::part2(input.as_ref())?)),
-            (3, 1) => Ok(Box::new(crate::day3::part1(input.as_ref())?)),
-            (3, 2) => Ok(Box::new(crate::day3::part2(input.as_ref())?)),
-            (4, 1) => Ok(Box::new(crate::day4::part1(input.as_ref())?)),
-            (4, 2) => Ok(Box::new(crate::day4::part2(input.as_ref())?)),
-            (5, 1) => Ok(Box::new(crate::day5::part1(input.as_ref())?)),
-            (5, 2) => Ok(Box::new(crate::day5::part2(input.as_ref())?)),
-            (6, 1) => Ok(Box::new(crate::day6::part1(input.as_ref())?)),
-            (6, 2) => Ok(Box::new(crate::day6::part2(input.as_ref())?)),
-            (7, 1) => Ok(Box::new(crate::day7::part1(input.as_ref())?)),
-            (7, 2) => Ok(Box::new(crate::day7::part2(input.as_ref())?)),
-            (8, 1) => Ok(Box::new(crate::day8::part1(input.as_ref())?)),
-            (8, 2) => Ok(Box::new(crate::day8::part2(input.as_ref())?)),
-            _ => bail!("day {} part {} is not implemented", self.day, self.part),
+    /// Parses a puzzle like '2022-7-2', or like '7-2' for a puzzle from `default_year`
+    pub fn parse_in(s: &str, default_year: u16) -> Result<Self, String> {
+        let (year, s) = split_year(s, default_year)?;
+        let parts = s.split_once('-');
+        if let Some((day, part)) = parts {
+            let day = parse_implemented_day(year, day)?;
+            let part = part.parse::<u16>();
+            if part.is_err() {
+                return Err("Day and part must be integers".into());
+            }
+            let part = part.unwrap();
+            if !(1..=2).contains(&part) {
+                return Err("Part must be either 1 or 2".into());
+            }
+            Ok(Self { year, day, part })
+        } else {
+            Err("puzzle must be given in the format {day}-{part} or {year}-{day}-{part}".into())
         }
     }
+
+    pub fn run(&self, source: &InputSource) -> Result<Answer> {
+        let input = self.parse(source)?;
+        self.solve(input.as_ref())
+    }
+
+    /// Reads and parses the input for the puzzle's day, ready for [`Puzzle::solve`]. Both
+    /// parts of a day can be solved with the same parsed input.
+    pub fn parse(&self, source: &InputSource) -> Result<Box<dyn Any>> {
+        self.solution()?;
+        let input = source.read(self.year, self.day)?;
+        self.parse_input(&input, source)
+    }
+
+    /// Parses input that has already been read from `source`, ready for [`Puzzle::solve`]
+    pub fn parse_input(&self, input: &str, source: &InputSource) -> Result<Box<dyn Any>> {
+        self.solution()?
+            .parse(input)
+            .map_err(|e| self.parse_context(e, source))
+    }
+
+    /// Solves the puzzle using input returned by [`Puzzle::parse`]
+    pub fn solve(&self, input: &dyn Any) -> Result<Answer> {
+        self.solution()?.solve(self.part, input)
+    }
+
+    /// Runs the puzzle, reading the input as it goes rather than all at once. Only some
+    /// days can do this.
+    pub fn run_streaming(&self, source: &InputSource) -> Result<Answer> {
+        let solution = self.solution()?;
+        let mut input = source.open(self.year, self.day)?;
+        solution
+            .run_streaming(self.part, &mut input)
+            .map_err(|e| self.parse_context(e, source))
+    }
+
+    fn solution(&self) -> Result<&'static registry::Entry> {
+        match registry::get(self.year, self.day) {
+            Some(solution) => Ok(solution),
+            None => bail!("{} day {} is not implemented", self.year, self.day),
+        }
+    }
+
+    /// Says which file could not be parsed, when the error came from parsing a file
+    fn parse_context(&self, e: anyhow::Error, source: &InputSource) -> anyhow::Error {
+        match (ParseError::find(&e), source.path(self.year, self.day)) {
+            (Some(_), Some(path)) => e.context(format!("could not parse {}", path.display())),
+            _ => e,
+        }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn day(&self) -> u16 {
+        self.day
+    }
+
+    pub fn part(&self) -> u16 {
+        self.part
+    }
+
+    /// All puzzles that have a solution, in year/day/part order
+    pub fn implemented() -> impl Iterator<Item = Puzzle> {
+        registry::solutions().iter().flat_map(|solution| {
+            (1..=2).map(|part| Puzzle {
+                year: solution.year,
+                day: solution.day,
+                part,
+            })
+        })
+    }
 }
 
 impl Display for Puzzle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "day {} part {}", self.day, self.part)
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)
     }
 }
 
 impl TryFrom<&str> for Puzzle {
     type Error = String;
 
+    /// Parses a puzzle, which is from [`DEFAULT_YEAR`] unless it gives a year
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let parts = s.split_once('-');
-        if let Some((day, part)) = parts {
-            let day = day.parse::<u16>();
-            let part = part.parse::<u16>();
-            if day.is_err() || part.is_err() {
-                return Err("Day and part must be integers".into());
-            }
-            let day = day.unwrap();
-            if !(1..=25).contains(&day) {
-                return Err("Day must be between 1 and 25".into());
-            }
-            let part = part.unwrap();
-            if !(1..=2).contains(&part) {
-                return Err("Part must be either 1 or 2".into());
+        Self::parse_in(s, DEFAULT_YEAR)
+    }
+}
+
+/// Splits the year from the start of a puzzle like '2022-7-2', returning `default_year`
+/// if there is none
+fn split_year(s: &str, default_year: u16) -> Result<(u16, &str), String> {
+    let Some((year, rest)) = s.split_once('-') else {
+        return Ok((default_year, s));
+    };
+    if year.len() != 4 || !year.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok((default_year, s));
+    }
+    let year = year.parse::<u16>().map_err(|e| e.to_string())?;
+    if year < 2015 {
+        return Err("Year must be 2015 or later".into());
+    }
+    Ok((year, rest))
+}
+
+fn parse_day(s: &str) -> Result<u16, String> {
+    let Ok(day) = s.parse::<u16>() else {
+        return Err("Day and part must be integers".into());
+    };
+    if !(1..=25).contains(&day) {
+        return Err("Day must be between 1 and 25".into());
+    }
+    Ok(day)
+}
+
+/// Parses the end of an exclusive range, which is the day after the last one in it
+fn parse_end_day(s: &str) -> Result<u16, String> {
+    let Ok(end) = s.parse::<u16>() else {
+        return Err("Day and part must be integers".into());
+    };
+    if !(1..=26).contains(&end) {
+        return Err("The end of a range must be between 1 and 26".into());
+    }
+    Ok(end)
+}
+
+fn parse_implemented_day(year: u16, s: &str) -> Result<u16, String> {
+    let day = parse_day(s)?;
+    if registry::get(year, day).is_none() {
+        return Err(format!(
+            "Day {} of {} is not implemented, use the 'list' command to see which days are",
+            day, year
+        ));
+    }
+    Ok(day)
+}
+
+/// One or more puzzles chosen on the command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selection {
+    /// A single day and part, e.g. '7-2'
+    Single(Puzzle),
+    /// Every implemented part of the days of `year` from `first` to `last` (inclusive)
+    Days { year: u16, first: u16, last: u16 },
+}
+
+impl Selection {
+    /// The puzzles to run. Ranges only include the implemented puzzles in them, and a single
+    /// puzzle is always implemented, as parsing rejects days that are not.
+    pub fn puzzles(&self) -> Vec<Puzzle> {
+        match self {
+            Self::Single(puzzle) => vec![*puzzle],
+            Self::Days { year, first, last } => Puzzle::implemented()
+                .filter(|p| p.year == *year && (*first..=*last).contains(&p.day))
+                .collect(),
+        }
+    }
+
+    /// Parses a selection, which can start with a year, like '2022-all' or '2022-3-*'. The
+    /// puzzles are from `default_year` if it does not.
+    pub fn parse_in(s: &str, default_year: u16) -> Result<Self, String> {
+        let (year, rest) = split_year(s, default_year)?;
+        let days = |first, last| Self::Days { year, first, last };
+        if rest == "all" {
+            return Ok(days(1, 25));
+        }
+        if let Some(day) = rest.strip_suffix("-*") {
+            let day = parse_implemented_day(year, day)?;
+            return Ok(days(day, day));
+        }
+        let range = rest
+            .split_once("..=")
+            .map(|(first, last)| (first, last, true))
+            .or_else(|| {
+                rest.split_once("..")
+                    .map(|(first, last)| (first, last, false))
+            });
+        if let Some((first, last, inclusive)) = range {
+            let first = parse_day(first)?;
+            let last = if inclusive {
+                parse_day(last)?
+            } else {
+                parse_end_day(last)? - 1
+            };
+            if first > last {
+                return Err("range must not be empty".into());
             }
-            Ok(Self { day, part })
-        } else {
-            Err("puzzle must be given in the format {day}-{part}".to_string())
+            return Ok(days(first, last));
         }
+        Ok(Self::Single(Puzzle::parse_in(s, default_year)?))
+    }
+}
+
+impl TryFrom<&str> for Selection {
+    type Error = String;
+
+    /// Parses a selection of puzzles from [`DEFAULT_YEAR`], unless it gives a year
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::parse_in(s, DEFAULT_YEAR)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn days(year: u16, first: u16, last: u16) -> Selection {
+        Selection::Days { year, first, last }
+    }
+
+    #[test]
+    fn test_can_parse_single_puzzle() {
+        let puzzle = Puzzle {
+            year: 2022,
+            day: 7,
+            part: 2,
+        };
+        assert_eq!(Selection::try_from("7-2"), Ok(Selection::Single(puzzle)));
+        assert_eq!(
+            Selection::try_from("2022-7-2"),
+            Ok(Selection::Single(puzzle))
+        );
+        assert_eq!(puzzle.to_string(), "2022 day 7 part 2");
+    }
+
+    #[test]
+    fn test_can_parse_day_ranges() {
+        assert_eq!(Selection::try_from("all"), Ok(days(2022, 1, 25)));
+        assert_eq!(Selection::try_from("3-*"), Ok(days(2022, 3, 3)));
+        assert_eq!(Selection::try_from("1..=8"), Ok(days(2022, 1, 8)));
+        assert_eq!(Selection::try_from("1..8"), Ok(days(2022, 1, 7)));
+        assert!(Selection::try_from("5..=2").is_err());
+        assert!(Selection::try_from("0..=2").is_err());
+        assert_eq!(Selection::try_from("1..=25"), Ok(days(2022, 1, 25)));
+        assert_eq!(Selection::try_from("1..26"), Ok(days(2022, 1, 25)));
+        assert!(Selection::try_from("1..=26").is_err());
+        assert!(Selection::try_from("1..27").is_err());
+        assert!(Selection::try_from("1..1").is_err());
+    }
+
+    #[test]
+    fn test_year_comes_from_the_puzzle_or_the_default() {
+        assert_eq!(Selection::parse_in("2023-all", 2022), Ok(days(2023, 1, 25)));
+        assert_eq!(Selection::parse_in("all", 2023), Ok(days(2023, 1, 25)));
+        assert_eq!(Selection::parse_in("2022-3-*", 2023), Ok(days(2022, 3, 3)));
+        assert_eq!(
+            Selection::parse_in("2021-1..=8", 2022),
+            Ok(days(2021, 1, 8))
+        );
+        assert_eq!(
+            Puzzle::parse_in("2022-1-2", 2023).map(|p| p.year()),
+            Ok(2022)
+        );
+        assert!(
+            Puzzle::parse_in("1-2", 2099).is_err(),
+            "2099 has no solutions"
+        );
+        assert!(Puzzle::parse_in("2014-1-2", 2022).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unimplemented_days() {
+        assert!(Selection::try_from("25-1").is_err());
+        assert!(Selection::try_from("25-*").is_err());
+        assert!(Selection::try_from("2099-1-*").is_err());
+    }
+
+    #[test]
+    fn test_ranges_only_contain_implemented_puzzles() {
+        let puzzles = days(2022, 1, 25).puzzles();
+        assert_eq!(puzzles.len(), registry::solutions_for(2022).count() * 2);
+        assert!(puzzles
+            .iter()
+            .all(|p| registry::get(p.year, p.day).is_some()));
+        assert!(days(2099, 1, 25).puzzles().is_empty());
+
+        let puzzle = |part| Puzzle {
+            year: 2022,
+            day: 2,
+            part,
+        };
+        assert_eq!(days(2022, 2, 2).puzzles(), vec![puzzle(1), puzzle(2)]);
     }
 }
@@ -0,0 +1,223 @@
+use std::any::Any;
+use std::io::BufRead;
+
+use anyhow::{bail, Context, Result};
+
+use crate::answer::Answer;
+use crate::solution::{Example, Solution, Streaming};
+
+/// Every implemented solution, in year and then day order
+static SOLUTIONS: &[Entry] = &[
+    Entry::of::<crate::y2022::day1::Day1>().streaming::<crate::y2022::day1::Day1>(),
+    Entry::of::<crate::y2022::day2::Day2>().streaming::<crate::y2022::day2::Day2>(),
+    Entry::of::<crate::y2022::day3::Day3>().streaming::<crate::y2022::day3::Day3>(),
+    Entry::of::<crate::y2022::day4::Day4>().streaming::<crate::y2022::day4::Day4>(),
+    Entry::of::<crate::y2022::day5::Day5>(),
+    Entry::of::<crate::y2022::day6::Day6>().streaming::<crate::y2022::day6::Day6>(),
+    Entry::of::<crate::y2022::day7::Day7>(),
+    Entry::of::<crate::y2022::day8::Day8>(),
+];
+
+/// All registered solutions, in year and then day order
+pub fn solutions() -> &'static [Entry] {
+    SOLUTIONS
+}
+
+/// Gets the solution for `day` of `year`, if there is one
+pub fn get(year: u16, day: u16) -> Option<&'static Entry> {
+    SOLUTIONS
+        .iter()
+        .find(|entry| entry.year == year && entry.day == day)
+}
+
+/// The registered solutions for `year`, in day order
+pub fn solutions_for(year: u16) -> impl Iterator<Item = &'static Entry> {
+    SOLUTIONS.iter().filter(move |entry| entry.year == year)
+}
+
+/// A type-erased [`Solution`], so that solutions with different input types can be stored
+/// together
+pub struct Entry {
+    pub year: u16,
+    pub day: u16,
+    pub title: &'static str,
+    pub examples: &'static [Example],
+    parse: fn(&str) -> Result<Box<dyn Any>>,
+    solve: fn(u16, &dyn Any) -> Result<Answer>,
+    stream: Option<StreamFn>,
+}
+
+/// Solves a part of a puzzle, reading the input as it goes
+type StreamFn = fn(u16, &mut dyn BufRead) -> Result<Answer>;
+
+impl Entry {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            title: S::TITLE,
+            examples: S::EXAMPLES,
+            parse: parse::<S>,
+            solve: solve::<S>,
+            stream: None,
+        }
+    }
+
+    /// Also lets the solution be run with [`Entry::run_streaming`]
+    pub const fn streaming<S: Streaming>(self) -> Self {
+        Self {
+            stream: Some(stream::<S>),
+            ..self
+        }
+    }
+
+    /// Whether the solution can read its input as it goes
+    pub fn can_stream(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Parses the raw puzzle input, ready to be passed to [`Entry::solve`]
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        (self.parse)(input)
+    }
+
+    /// Solves `part` using input returned by [`Entry::parse`]
+    pub fn solve(&self, part: u16, input: &dyn Any) -> Result<Answer> {
+        (self.solve)(part, input)
+    }
+
+    /// Parses the raw puzzle input and solves `part`
+    pub fn run(&self, part: u16, input: &str) -> Result<Answer> {
+        let parsed = self.parse(input)?;
+        self.solve(part, parsed.as_ref())
+    }
+
+    /// Solves `part`, reading the input from `input` as it goes rather than all at once
+    pub fn run_streaming(&self, part: u16, input: &mut dyn BufRead) -> Result<Answer> {
+        let Some(stream) = self.stream else {
+            bail!(
+                "{} day {} needs all of its input at once, so cannot be streamed",
+                self.year,
+                self.day
+            );
+        };
+        stream(part, input)
+    }
+}
+
+fn parse<S: Solution>(input: &str) -> Result<Box<dyn Any>> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn solve<S: Solution>(part: u16, input: &dyn Any) -> Result<Answer> {
+    let input = input
+        .downcast_ref::<S::Input>()
+        .context("input was not parsed by the same solution")?;
+    match part {
+        1 => S::part1(input),
+        2 => S::part2(input),
+        _ => bail!("{} day {} has no part {}", S::YEAR, S::DAY, part),
+    }
+}
+
+fn stream<S: Streaming>(part: u16, input: &mut dyn BufRead) -> Result<Answer> {
+    match part {
+        1 => S::part1_from(input),
+        2 => S::part2_from(input),
+        _ => bail!("{} day {} has no part {}", S::YEAR, S::DAY, part),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::ParseError;
+
+    #[test]
+    fn test_examples_give_expected_answers() {
+        for solution in solutions() {
+            for example in solution.examples {
+                for part in 1..=2 {
+                    let Some(expected) = example.answer(part) else {
+                        continue;
+                    };
+                    let answer = solution.run(part, example.input).unwrap();
+                    assert_eq!(
+                        answer, expected,
+                        "{} day {} part {}",
+                        solution.year, solution.day, part
+                    );
+                }
+            }
+        }
+    }
+
+    /// Broken versions of each of the solution's examples
+    fn broken_inputs(solution: &Entry) -> Vec<String> {
+        let mut inputs = vec![String::new(), "\n".into(), "\u{feff}garbage ✓\n".into()];
+        for example in solution.examples {
+            // Every truncated version of the example, plus some with lines mixed up
+            inputs.extend(
+                (0..example.input.len()).filter_map(|n| example.input.get(..n).map(str::to_owned)),
+            );
+            inputs.push(example.input.replace('1', "x"));
+            inputs.push(example.input.lines().rev().collect::<Vec<_>>().join("\n"));
+        }
+        inputs
+    }
+
+    #[test]
+    fn test_broken_input_gives_errors_not_panics() {
+        for solution in solutions() {
+            for input in broken_inputs(solution) {
+                for part in 1..=2 {
+                    // Only checking that this returns rather than panicking
+                    let _ = solution.run(part, &input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_gives_the_same_results() {
+        for solution in solutions().iter().filter(|s| s.can_stream()) {
+            let mut inputs = broken_inputs(solution);
+            inputs.extend(solution.examples.iter().map(|e| e.input.to_owned()));
+            for seed in 0..5 {
+                let mut input = Vec::new();
+                crate::generate::generate(solution.year, solution.day, 30, seed, &mut input)
+                    .unwrap();
+                inputs.push(String::from_utf8(input).unwrap());
+            }
+            for input in inputs {
+                for part in 1..=2 {
+                    let whole = solution.run(part, &input);
+                    let streamed = solution.run_streaming(part, &mut input.as_bytes());
+                    let context = format!(
+                        "{} day {} part {} on {:?}",
+                        solution.year, solution.day, part, input
+                    );
+                    match (whole, streamed) {
+                        (Ok(whole), Ok(streamed)) => assert_eq!(whole, streamed, "{}", context),
+                        (Err(whole), Err(streamed)) => assert_eq!(
+                            ParseError::find(&whole).map(|e| (e.line, e.column)),
+                            ParseError::find(&streamed).map(|e| (e.line, e.column)),
+                            "{}",
+                            context
+                        ),
+                        (whole, streamed) => {
+                            panic!("{}: {:?} but streamed {:?}", context, whole, streamed)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_some_days_can_be_streamed() {
+        let day5 = get(2022, 5).unwrap();
+        assert!(!day5.can_stream());
+        assert!(day5.run_streaming(1, &mut "".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,390 @@
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use advent_of_code_2022::answer::Answer;
+use advent_of_code_2022::parse::ParseError;
+use advent_of_code_2022::puzzle::Puzzle;
+use advent_of_code_2022::runner::Outcome;
+use advent_of_code_2022::runs::{RunLog, Trend};
+
+/// How the results of running puzzles are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// A table for people to read
+    Text,
+    /// A JSON array with one object per puzzle
+    Json,
+    /// Tab separated values with a header row
+    Tsv,
+}
+
+/// When text output is colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    /// Only when printing to a terminal, and NO_COLOR is not set
+    Auto,
+    Always,
+    Never,
+}
+
+/// Colors for text output, which leave the text as it is when color is turned off
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    pub fn new(color: Color) -> Self {
+        let enabled = match color {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+        };
+        Self { enabled }
+    }
+
+    /// Green, for puzzles that gave the right answer
+    pub fn good(&self, text: &str) -> String {
+        self.paint(text, "32")
+    }
+
+    /// Red, for puzzles that failed
+    pub fn bad(&self, text: &str) -> String {
+        self.paint(text, "31")
+    }
+
+    fn paint(&self, text: &str, code: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+/// Prints how long running the puzzles took in total, compared to how long it would have
+/// taken to run them one after another
+pub fn print_timing(outcomes: &[Outcome], wall: Duration, threads: usize) {
+    let summed: Duration = outcomes
+        .iter()
+        .map(|o| o.elapsed + o.parse.unwrap_or_default())
+        .sum();
+    println!();
+    println!(
+        "Ran {} puzzles in {:.3?} on {} thread{} ({:.3?} summed across puzzles, {:.1}x speedup)",
+        outcomes.len(),
+        wall,
+        threads,
+        if threads == 1 { "" } else { "s" },
+        summed,
+        summed.as_secs_f64() / wall.as_secs_f64(),
+    );
+}
+
+pub fn print(outcomes: &[Outcome], format: Format, palette: Palette) {
+    match format {
+        Format::Text => print_table(outcomes, palette),
+        Format::Json => print_json(outcomes),
+        Format::Tsv => print_tsv(outcomes),
+    }
+}
+
+/// Prints a table with one row per outcome. Multi-line answers are drawn below their row,
+/// and failed puzzles are listed with their errors after the table. Reading and parsing the
+/// input is timed separately from solving, and only for the first part run for each day.
+fn print_table(outcomes: &[Outcome], palette: Palette) {
+    let answers: Vec<String> = outcomes
+        .iter()
+        .map(|o| match &o.answer {
+            Ok(Answer::Multiline(lines)) => format!("({} lines)", lines.len()),
+            Ok(answer) => answer.to_string(),
+            Err(_) => "FAILED".to_string(),
+        })
+        .collect();
+    let answer_width = answers.iter().map(|a| a.len()).max().unwrap_or(0).max(6);
+
+    println!(
+        "{:>4}  {:>3}  {:>4}  {:<answer_width$}  {:>12}  {:>12}",
+        "Year", "Day", "Part", "Answer", "Parse", "Solve"
+    );
+    for (outcome, answer) in outcomes.iter().zip(answers.iter()) {
+        // Padded before it is colored, as the color codes take up no space
+        let mut answer = format!("{:<answer_width$}", answer);
+        if outcome.answer.is_err() {
+            answer = palette.bad(&answer);
+        }
+        println!(
+            "{:>4}  {:>3}  {:>4}  {}  {:>12}  {:>12}",
+            outcome.puzzle.year(),
+            outcome.puzzle.day(),
+            outcome.puzzle.part(),
+            answer,
+            outcome
+                .parse
+                .map(|parse| format!("{:.3?}", parse))
+                .unwrap_or_default(),
+            format!("{:.3?}", outcome.elapsed),
+        );
+        if let Ok(Answer::Multiline(lines)) = &outcome.answer {
+            for line in lines {
+                println!("{:>17}{}", "", line);
+            }
+        }
+    }
+
+    let failures: Vec<_> = outcomes
+        .iter()
+        .filter_map(|o| o.answer.as_ref().err().map(|e| (o.puzzle, e)))
+        .collect();
+    if !failures.is_empty() {
+        println!();
+        println!("Failures:");
+        for (puzzle, err) in failures {
+            println!("  {}: {:#}", palette.bad(&puzzle.to_string()), err);
+            if let Some(parse_error) = ParseError::find(err) {
+                for line in parse_error.snippet().lines() {
+                    println!("    {}", line);
+                }
+            }
+        }
+    }
+}
+
+/// The result of running a puzzle, as written by the machine-readable formats
+#[derive(Debug, PartialEq, Serialize)]
+struct Record {
+    year: u16,
+    day: u16,
+    part: u16,
+    answer: Option<String>,
+    /// The answer as a JSON number if it is an integer, an array of lines if it is drawn over
+    /// several lines, otherwise the same as `answer`
+    value: Option<Answer>,
+    /// How long solving the puzzle took, not counting reading and parsing the input
+    duration_ns: u64,
+    /// How long reading and parsing the input took, which is only timed for the first part
+    /// run for each day
+    parse_ns: Option<u64>,
+    error: Option<ErrorRecord>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct ErrorRecord {
+    message: String,
+    /// The errors that caused this one, outermost first
+    causes: Vec<String>,
+}
+
+impl From<&Outcome> for Record {
+    fn from(outcome: &Outcome) -> Self {
+        let (value, error) = match &outcome.answer {
+            Ok(answer) => (Some(answer.clone()), None),
+            Err(e) => (
+                None,
+                Some(ErrorRecord {
+                    message: e.to_string(),
+                    causes: e.chain().skip(1).map(|cause| cause.to_string()).collect(),
+                }),
+            ),
+        };
+        Self {
+            year: outcome.puzzle.year(),
+            day: outcome.puzzle.day(),
+            part: outcome.puzzle.part(),
+            answer: value.as_ref().map(Answer::to_string),
+            value,
+            duration_ns: outcome.elapsed.as_nanos() as u64,
+            parse_ns: outcome.parse.map(|parse| parse.as_nanos() as u64),
+            error,
+        }
+    }
+}
+
+fn print_json(outcomes: &[Outcome]) {
+    let records: Vec<Record> = outcomes.iter().map(Record::from).collect();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&records).expect("records can always be serialized")
+    );
+}
+
+fn print_tsv(outcomes: &[Outcome]) {
+    println!("year\tday\tpart\tanswer\tduration_ns\tparse_ns\terror");
+    for record in outcomes.iter().map(Record::from) {
+        let error = record
+            .error
+            .map(|e| {
+                std::iter::once(e.message)
+                    .chain(e.causes)
+                    .collect::<Vec<_>>()
+                    .join(": ")
+            })
+            .unwrap_or_default();
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            record.year,
+            record.day,
+            record.part,
+            escape_tsv(&record.answer.unwrap_or_default()),
+            record.duration_ns,
+            record.parse_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+            escape_tsv(&error),
+        );
+    }
+}
+
+/// Escapes characters that would otherwise break up a TSV field
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Prints how each puzzle's answer and runtime have changed over the runs in `log`,
+/// flagging puzzles that got more than `threshold` percent slower at the latest commit
+pub fn print_history(puzzles: &[Puzzle], log: &RunLog, threshold: f64, palette: Palette) {
+    let trends: Vec<Trend> = puzzles
+        .iter()
+        .filter_map(|puzzle| log.trend(*puzzle, 1.0 + threshold / 100.0))
+        .collect();
+    if trends.is_empty() {
+        println!("None of the puzzles have been run yet");
+        return;
+    }
+    let commit = |commit: &Option<String>| commit.clone().unwrap_or("an unknown commit".into());
+    for trend in trends.iter() {
+        println!(
+            "{}: {} run{}",
+            trend.puzzle,
+            trend.runs,
+            if trend.runs == 1 { "" } else { "s" }
+        );
+        for (i, change) in trend.answers.iter().enumerate() {
+            let answer = match change.answer.lines().count() {
+                0 | 1 => change.answer.clone(),
+                lines => format!("({} lines)", lines),
+            };
+            let changed = if i == 0 { "" } else { " changed to" };
+            println!(
+                "  answer{} {} at {}",
+                changed,
+                answer,
+                commit(&change.commit)
+            );
+        }
+        let [.., latest] = trend.timings.as_slice() else {
+            continue;
+        };
+        print!(
+            "  runtime {} {:.3?} at {}",
+            trend.sparkline(),
+            latest.median,
+            commit(&latest.commit)
+        );
+        if let (Some(slowdown), [.., previous, _]) = (trend.slowdown, trend.timings.as_slice()) {
+            let slower = format!("{:.0}% slower", (slowdown - 1.0) * 100.0);
+            print!(
+                ", {} than {:.3?} at {}",
+                palette.bad(&slower),
+                previous.median,
+                commit(&previous.commit)
+            );
+        }
+        println!();
+    }
+
+    let slower = trends.iter().filter(|t| t.slowdown.is_some()).count();
+    if slower > 0 {
+        println!();
+        println!("{} of {} puzzles got slower", slower, trends.len());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::{anyhow, Context};
+
+    use super::*;
+    use advent_of_code_2022::puzzle::Puzzle;
+
+    fn outcome(answer: anyhow::Result<Answer>) -> Outcome {
+        Outcome {
+            puzzle: Puzzle::try_from("5-1").unwrap(),
+            answer,
+            elapsed: Duration::from_micros(12),
+            parse: Some(Duration::from_micros(30)),
+            input_hash: None,
+        }
+    }
+
+    #[test]
+    fn test_integer_answers_have_a_numeric_value() {
+        let record = Record::from(&outcome(Ok(24000.into())));
+        assert_eq!(record.answer, Some("24000".into()));
+        assert_eq!(
+            serde_json::to_value(&record.value).unwrap(),
+            serde_json::json!(24000)
+        );
+        assert_eq!((record.year, record.day, record.part), (2022, 5, 1));
+        assert_eq!(record.duration_ns, 12000);
+        assert_eq!(record.parse_ns, Some(30000));
+    }
+
+    #[test]
+    fn test_string_answers_have_a_string_value() {
+        let record = Record::from(&outcome(Ok("CMZ".into())));
+        assert_eq!(
+            serde_json::to_value(&record.value).unwrap(),
+            serde_json::json!("CMZ")
+        );
+    }
+
+    #[test]
+    fn test_multiline_answers_have_an_array_value() {
+        let record = Record::from(&outcome(Ok("#..#\n####".into())));
+        assert_eq!(record.answer, Some("#..#\n####".into()));
+        assert_eq!(
+            serde_json::to_value(&record.value).unwrap(),
+            serde_json::json!(["#..#", "####"])
+        );
+    }
+
+    #[test]
+    fn test_errors_include_their_causes() {
+        let err = Err::<(), _>(anyhow!("No such file"))
+            .context("could not read input file")
+            .unwrap_err();
+        let record = Record::from(&outcome(Err(err)));
+        assert_eq!(record.answer, None);
+        assert_eq!(
+            record.error,
+            Some(ErrorRecord {
+                message: "could not read input file".into(),
+                causes: vec!["No such file".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_palette_only_colors_when_enabled() {
+        assert_eq!(Palette::new(Color::Never).bad("FAIL"), "FAIL");
+        assert_eq!(
+            Palette::new(Color::Always).bad("FAIL"),
+            "\x1b[31mFAIL\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_tsv_fields_are_escaped() {
+        assert_eq!(escape_tsv("a\tb\nc"), "a\\tb\\nc");
+    }
+}
@@ -0,0 +1,159 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+
+use crate::answer::Answer;
+use crate::input::{self, InputSource};
+use crate::puzzle::Puzzle;
+
+/// The result of running a single puzzle, along with how long it took
+pub struct Outcome {
+    pub puzzle: Puzzle,
+    pub answer: Result<Answer>,
+    /// How long solving the puzzle took, not counting reading and parsing the input
+    pub elapsed: Duration,
+    /// How long reading and parsing the input took. Only the first part run for each day
+    /// has this, as the other parts of the day share its input.
+    pub parse: Option<Duration>,
+    /// A hash of the input that the puzzle was solved with, made by [`input::hash`]
+    pub input_hash: Option<u64>,
+}
+
+/// Runs the puzzles on up to `jobs` threads, carrying on past any that fail. Each day's
+/// input is parsed once for all of its parts, which run one after another on the same
+/// thread, so no more threads are used than there are days. The outcomes are returned in the
+/// same order as `puzzles`, along with the number of threads that ran them.
+pub fn run_all(puzzles: &[Puzzle], source: &InputSource, jobs: usize) -> (Vec<Outcome>, usize) {
+    let days: Vec<&[Puzzle]> = puzzles
+        .chunk_by(|a, b| (a.year(), a.day()) == (b.year(), b.day()))
+        .collect();
+    let next = AtomicUsize::new(0);
+    let threads = jobs.clamp(1, days.len().max(1));
+
+    let mut outcomes: Vec<(usize, Vec<Outcome>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outcomes = Vec::new();
+                    // Each thread takes the next day that nobody has started yet
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(day) = days.get(i) else {
+                            break;
+                        };
+                        outcomes.push((i, run_day(day, source)));
+                    }
+                    outcomes
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("a puzzle panicked"))
+            .collect()
+    });
+
+    outcomes.sort_by_key(|(i, _)| *i);
+    let outcomes = outcomes
+        .into_iter()
+        .flat_map(|(_, outcomes)| outcomes)
+        .collect();
+    (outcomes, threads)
+}
+
+/// Runs puzzles that are all from the same day, parsing the input once
+fn run_day(puzzles: &[Puzzle], source: &InputSource) -> Vec<Outcome> {
+    let first = puzzles[0];
+    let start = Instant::now();
+    let parsed = catch_panic(first, || {
+        let text = source.read(first.year(), first.day())?;
+        Ok((first.parse_input(&text, source)?, text))
+    });
+    let mut parse = Some(start.elapsed());
+    let (input, input_hash, mut parse_error) = match parsed {
+        Ok((input, text)) => (Some(input), Some(input::hash(&text)), None),
+        Err(e) => (None, None, Some(e)),
+    };
+    let mut outcomes = Vec::with_capacity(puzzles.len());
+    for puzzle in puzzles.iter().copied() {
+        let start = Instant::now();
+        let answer = match (&input, parse_error.take()) {
+            (Some(input), _) => catch_panic(puzzle, || puzzle.solve(input.as_ref())),
+            (None, Some(e)) => Err(e),
+            // Errors cannot be copied, so later parts get theirs by trying again
+            (None, None) => catch_panic(puzzle, || puzzle.run(source)),
+        };
+        outcomes.push(Outcome {
+            puzzle,
+            answer,
+            elapsed: start.elapsed(),
+            parse: parse.take(),
+            input_hash,
+        });
+    }
+    outcomes
+}
+
+/// Runs `f`, turning a panic into an error. A bug in one puzzle should not stop the others
+/// from running.
+pub fn catch_panic<T>(puzzle: Puzzle, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(anyhow!("{} panicked: {}", puzzle, panic_message(&payload))))
+}
+
+/// The message that a panic was started with, if it was given one
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::ParseError;
+
+    #[test]
+    fn test_outcomes_are_in_the_same_order_as_the_puzzles() {
+        let source = InputSource::Text("A Y\nB X\nC Z".into());
+        let puzzles: Vec<_> = ["2-2", "2-1", "2-2", "2-1", "2-1"]
+            .into_iter()
+            .map(|p| Puzzle::try_from(p).unwrap())
+            .collect();
+        for jobs in [1, 2, 8] {
+            let (outcomes, threads) = run_all(&puzzles, &source, jobs);
+            assert_eq!(threads, 1, "the puzzles are all from one day");
+            let answers: Vec<_> = outcomes
+                .iter()
+                .map(|o| o.answer.as_ref().unwrap().to_string())
+                .collect();
+            assert_eq!(answers, vec!["12", "15", "12", "15", "15"]);
+            assert!(outcomes
+                .iter()
+                .zip(puzzles.iter())
+                .all(|(o, p)| o.puzzle == *p));
+            let parsed: Vec<_> = outcomes.iter().map(|o| o.parse.is_some()).collect();
+            assert_eq!(parsed, vec![true, false, false, false, false]);
+            let hash = Some(input::hash("A Y\nB X\nC Z"));
+            assert!(outcomes.iter().all(|o| o.input_hash == hash));
+        }
+    }
+
+    #[test]
+    fn test_every_part_of_a_day_reports_a_parse_error() {
+        let source = InputSource::Text("A Y\nB Q\n".into());
+        let puzzles: Vec<_> = Puzzle::implemented().filter(|p| p.day() == 2).collect();
+        let (outcomes, _) = run_all(&puzzles, &source, 1);
+        assert_eq!(outcomes.len(), 2);
+        for outcome in outcomes {
+            let err = outcome.answer.unwrap_err();
+            assert_eq!(ParseError::find(&err).map(|e| e.line), Some(2));
+        }
+    }
+}
@@ -0,0 +1,421 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::bench::Stats;
+use crate::puzzle::Puzzle;
+use crate::runner::Outcome;
+
+/// A puzzle that was solved, as recorded in the run log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub year: u16,
+    pub day: u16,
+    pub part: u16,
+    pub answer: String,
+    /// How long solving the puzzle took, in nanoseconds
+    pub elapsed_ns: u64,
+    /// Whether `elapsed_ns` includes reading and parsing the input, which only the first
+    /// part run for each day does
+    pub parsed: bool,
+    /// The git commit that was checked out, ending in '-dirty' if there were changes that
+    /// had not been committed
+    pub commit: Option<String>,
+    /// A hash of the input, so that runs on different input are not compared. Input that is
+    /// read as the puzzle runs is never all in memory, so has no hash.
+    pub input_hash: Option<String>,
+    /// When the puzzle was run, in seconds since the Unix epoch
+    pub ran_at: u64,
+}
+
+impl Run {
+    /// The runs of the puzzles in `outcomes` that gave an answer, which were run at `commit`
+    pub fn from_outcomes(outcomes: &[Outcome], commit: Option<&str>, ran_at: u64) -> Vec<Self> {
+        let mut runs = Vec::new();
+        for outcome in outcomes {
+            let puzzle = outcome.puzzle;
+            let Ok(answer) = &outcome.answer else {
+                continue;
+            };
+            runs.push(Self {
+                year: puzzle.year(),
+                day: puzzle.day(),
+                part: puzzle.part(),
+                answer: answer.to_string(),
+                elapsed_ns: (outcome.elapsed + outcome.parse.unwrap_or_default())
+                    .as_nanos()
+                    .try_into()
+                    .unwrap_or(u64::MAX),
+                parsed: outcome.parse.is_some(),
+                commit: commit.map(str::to_owned),
+                input_hash: outcome.input_hash.map(|hash| format!("{:016x}", hash)),
+                ran_at,
+            });
+        }
+        runs
+    }
+
+    /// A run of a puzzle that read its input as it went, so the time includes reading it
+    pub fn streamed(
+        puzzle: Puzzle,
+        answer: &Answer,
+        elapsed: Duration,
+        commit: Option<&str>,
+        ran_at: u64,
+    ) -> Self {
+        Self {
+            year: puzzle.year(),
+            day: puzzle.day(),
+            part: puzzle.part(),
+            answer: answer.to_string(),
+            elapsed_ns: elapsed.as_nanos().try_into().unwrap_or(u64::MAX),
+            parsed: true,
+            commit: commit.map(str::to_owned),
+            input_hash: None,
+            ran_at,
+        }
+    }
+
+    fn is(&self, puzzle: Puzzle) -> bool {
+        (self.year, self.day, self.part) == (puzzle.year(), puzzle.day(), puzzle.part())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_ns)
+    }
+}
+
+/// Every puzzle that has been solved, stored as one JSON object per line
+pub struct RunLog {
+    runs: Vec<Run>,
+}
+
+impl RunLog {
+    /// Loads the log from `path`. A missing file is treated as an empty log.
+    pub fn load(path: &Path) -> Result<Self> {
+        let runs = match std::fs::read_to_string(path) {
+            Ok(contents) => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(i, line)| {
+                    serde_json::from_str(line).with_context(|| {
+                        format!("could not parse line {} of {}", i + 1, path.display())
+                    })
+                })
+                .collect::<Result<_>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e).with_context(|| format!("could not read {}", path.display())),
+        };
+        Ok(Self { runs })
+    }
+
+    /// Adds runs to the end of the log file at `path`, without reading what is already
+    /// there
+    pub fn append(path: &Path, runs: &[Run]) -> Result<()> {
+        if runs.is_empty() {
+            return Ok(());
+        }
+        let mut lines = String::new();
+        for run in runs {
+            lines.push_str(&serde_json::to_string(run)?);
+            lines.push('\n');
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .with_context(|| format!("could not write to {}", path.display()))
+    }
+
+    /// The latest run of `puzzle`, if it has been run
+    pub fn last(&self, puzzle: Puzzle) -> Option<&Run> {
+        self.runs.iter().rev().find(|run| run.is(puzzle))
+    }
+
+    /// How `puzzle`'s answer and runtime have changed, if it has ever been run. A slowdown
+    /// is reported when the latest commit's runs took more than `threshold` times as long
+    /// as the previous commit's.
+    pub fn trend(&self, puzzle: Puzzle, threshold: f64) -> Option<Trend> {
+        let runs: Vec<&Run> = self.runs.iter().filter(|run| run.is(puzzle)).collect();
+        let latest = *runs.last()?;
+
+        let mut answers: Vec<Change> = Vec::new();
+        for run in runs.iter() {
+            if answers.last().map(|change| &change.answer) != Some(&run.answer) {
+                answers.push(Change {
+                    answer: run.answer.clone(),
+                    commit: run.commit.clone(),
+                });
+            }
+        }
+
+        // Runtimes are only comparable with the same input, and if they all include
+        // parsing it or all do not
+        let mut commits: Vec<(Option<String>, Vec<Duration>)> = Vec::new();
+        let comparable = runs
+            .iter()
+            .filter(|run| run.parsed == latest.parsed && run.input_hash == latest.input_hash);
+        for run in comparable {
+            match commits.last_mut() {
+                Some((commit, times)) if *commit == run.commit => times.push(run.elapsed()),
+                _ => commits.push((run.commit.clone(), vec![run.elapsed()])),
+            }
+        }
+        let timings: Vec<Timing> = commits
+            .into_iter()
+            .map(|(commit, times)| Timing {
+                commit,
+                median: Stats::from_samples(&times).median,
+            })
+            .collect();
+
+        // Runs without a hash may have read different input, and nothing can be said to be
+        // some number of times slower than no time at all
+        let slowdown = match timings.as_slice() {
+            [.., previous, last] if latest.input_hash.is_some() && !previous.median.is_zero() => {
+                let ratio = last.median.as_secs_f64() / previous.median.as_secs_f64();
+                (ratio > threshold).then_some(ratio)
+            }
+            _ => None,
+        };
+
+        Some(Trend {
+            puzzle,
+            runs: runs.len(),
+            answers,
+            timings,
+            slowdown,
+        })
+    }
+}
+
+/// How a puzzle's answer and runtime have changed over its runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    pub puzzle: Puzzle,
+    /// How many times it has been run
+    pub runs: usize,
+    /// The first answer, then each time it gave a different one
+    pub answers: Vec<Change>,
+    /// The median runtime of the runs at each commit, oldest first. Only runs that are
+    /// comparable with the latest one are included.
+    pub timings: Vec<Timing>,
+    /// How many times longer the latest commit's runs took than the previous commit's, if
+    /// that was over the threshold. Runs on input without a hash are never compared.
+    pub slowdown: Option<f64>,
+}
+
+/// An answer that a puzzle gave, and the commit it first gave it at
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub answer: String,
+    pub commit: Option<String>,
+}
+
+/// How long a puzzle usually took to run at a commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub commit: Option<String>,
+    pub median: Duration,
+}
+
+impl Trend {
+    /// The timings as a line of block characters, the highest for the slowest
+    pub fn sparkline(&self) -> String {
+        const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let medians = || self.timings.iter().map(|timing| timing.median);
+        let (Some(min), Some(max)) = (medians().min(), medians().max()) else {
+            return String::new();
+        };
+        let range = (max - min).as_secs_f64();
+        medians()
+            .map(|median| {
+                if range == 0.0 {
+                    return BLOCKS[0];
+                }
+                let level = (median - min).as_secs_f64() / range * (BLOCKS.len() - 1) as f64;
+                BLOCKS[level.round() as usize]
+            })
+            .collect()
+    }
+}
+
+/// The commit of this project that is checked out, ending in '-dirty' if there are changes
+/// to tracked files that have not been committed, or `None` if it is not in a git
+/// repository. It is only looked up once, as the code that is running cannot change.
+pub fn git_commit() -> Option<&'static str> {
+    static COMMIT: OnceLock<Option<String>> = OnceLock::new();
+    COMMIT.get_or_init(find_git_commit).as_deref()
+}
+
+fn find_git_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+    };
+    let head = git(&["rev-parse", "--short", "HEAD"])?;
+    let mut commit = String::from_utf8(head.stdout).ok()?.trim().to_owned();
+    let status = git(&["status", "--porcelain", "--untracked-files=no"])?;
+    if !status.stdout.is_empty() {
+        commit.push_str("-dirty");
+    }
+    Some(commit)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn puzzle(s: &str) -> Puzzle {
+        Puzzle::try_from(s).unwrap()
+    }
+
+    fn run(answer: &str, millis: u64, commit: &str) -> Run {
+        Run {
+            year: 2022,
+            day: 1,
+            part: 1,
+            answer: answer.into(),
+            elapsed_ns: millis * 1_000_000,
+            parsed: true,
+            commit: Some(commit.into()),
+            input_hash: Some("0123456789abcdef".into()),
+            ran_at: 0,
+        }
+    }
+
+    fn log(runs: Vec<Run>) -> RunLog {
+        RunLog { runs }
+    }
+
+    #[test]
+    fn test_only_the_first_part_of_a_day_includes_parsing() {
+        let outcome = |p, answer: Result<u64>, parse: bool| Outcome {
+            puzzle: puzzle(p),
+            answer: answer.map(Into::into),
+            elapsed: Duration::from_millis(2),
+            parse: parse.then_some(Duration::from_millis(1)),
+            input_hash: Some(0x1234),
+        };
+        let outcomes = [
+            outcome("1-1", Ok(1), true),
+            outcome("1-2", Ok(2), false),
+            outcome("2-1", Err(anyhow!("failed")), true),
+            outcome("2-2", Ok(4), false),
+        ];
+        let runs = Run::from_outcomes(&outcomes, Some("abc1234"), 100);
+        let parsed: Vec<_> = runs.iter().map(|r| (r.day, r.part, r.parsed)).collect();
+        assert_eq!(parsed, vec![(1, 1, true), (1, 2, false), (2, 2, false)]);
+        assert_eq!(runs[0].answer, "1");
+        assert_eq!(runs[0].elapsed(), Duration::from_millis(3));
+        assert_eq!(runs[1].elapsed(), Duration::from_millis(2));
+        assert_eq!(runs[0].input_hash.as_deref(), Some("0000000000001234"));
+    }
+
+    #[test]
+    fn test_trend_shows_answer_changes() {
+        let log = log(vec![
+            run("1", 1, "a"),
+            run("1", 1, "b"),
+            run("2", 1, "c"),
+            run("1", 1, "d"),
+        ]);
+        let trend = log.trend(puzzle("1-1"), 1.2).unwrap();
+        assert_eq!(trend.runs, 4);
+        let changes: Vec<_> = trend
+            .answers
+            .iter()
+            .map(|c| (c.answer.as_str(), c.commit.as_deref()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![("1", Some("a")), ("2", Some("c")), ("1", Some("d"))]
+        );
+        assert!(log.trend(puzzle("1-2"), 1.2).is_none());
+    }
+
+    #[test]
+    fn test_trend_flags_slowdowns_between_commits() {
+        let runs = vec![
+            run("1", 10, "a"),
+            run("1", 12, "a"),
+            run("1", 11, "a"),
+            run("1", 15, "b"),
+            run("1", 14, "b"),
+        ];
+        let trend = log(runs.clone()).trend(puzzle("1-1"), 1.2).unwrap();
+        let medians: Vec<_> = trend.timings.iter().map(|t| t.median.as_micros()).collect();
+        assert_eq!(
+            medians,
+            vec![11_000, 14_500],
+            "even counts average the middle two"
+        );
+        let slowdown = trend.slowdown.unwrap();
+        assert!((slowdown - 14.5 / 11.0).abs() < 1e-9);
+        assert_eq!(trend.sparkline(), "▁█");
+
+        assert_eq!(log(runs).trend(puzzle("1-1"), 1.5).unwrap().slowdown, None);
+
+        let instant = vec![run("1", 0, "a"), run("1", 5, "b")];
+        assert_eq!(
+            log(instant).trend(puzzle("1-1"), 1.2).unwrap().slowdown,
+            None
+        );
+    }
+
+    #[test]
+    fn test_trend_only_compares_runs_like_the_latest() {
+        let mut unparsed = run("1", 1, "b");
+        unparsed.parsed = false;
+        let mut other_input = run("1", 100, "c");
+        other_input.input_hash = None;
+        let log = log(vec![
+            run("1", 10, "a"),
+            unparsed,
+            run("1", 20, "c"),
+            other_input,
+        ]);
+        let trend = log.trend(puzzle("1-1"), 1.2).unwrap();
+        assert_eq!(trend.timings.len(), 1);
+        assert_eq!(trend.slowdown, None);
+
+        // Streamed runs have no hash, so could have read anything
+        let streamed = |millis, commit| Run {
+            input_hash: None,
+            ..run("1", millis, commit)
+        };
+        let log = self::log(vec![streamed(10, "a"), streamed(100, "b")]);
+        let trend = log.trend(puzzle("1-1"), 1.2).unwrap();
+        assert_eq!(trend.timings.len(), 2);
+        assert_eq!(trend.slowdown, None);
+    }
+
+    #[test]
+    fn test_appends_runs_to_the_file() {
+        let dir = std::env::temp_dir().join(format!("aoc-runs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("runs.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        assert!(RunLog::load(&path).unwrap().runs.is_empty());
+        RunLog::append(&path, &[run("1", 1, "a")]).unwrap();
+        RunLog::append(&path, &[run("2", 2, "b")]).unwrap();
+        let loaded = RunLog::load(&path).unwrap();
+        assert_eq!(loaded.runs, vec![run("1", 1, "a"), run("2", 2, "b")]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,277 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+
+/// The files that were changed when adding a new day
+#[derive(Debug)]
+pub struct Scaffolded {
+    pub created: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+    /// Files that already existed, so were left alone
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Adds a module for `day` of `year` to the project in `root`, registers it with the runner
+/// and creates an empty input file for it. The module for the year is added too, if this is
+/// its first day. Fails without changing anything if the day's module already exists.
+pub fn new_day(root: &Path, year: u16, day: u16) -> Result<Scaffolded> {
+    if !(1..=25).contains(&day) {
+        bail!("Day must be between 1 and 25");
+    }
+    let src = root.join("src");
+    let year_dir = src.join(format!("y{}", year));
+    let module = year_dir.join(format!("day{}.rs", day));
+    let year_module = year_dir.join("mod.rs");
+    let lib = src.join("lib.rs");
+    let registry = src.join("registry.rs");
+    let input = root
+        .join("input")
+        .join(year.to_string())
+        .join(format!("day{}.txt", day));
+
+    if module.exists() {
+        bail!("{} already exists", module.display());
+    }
+    let mut updates = Vec::new();
+    let new_year = !year_module.exists();
+    if new_year {
+        let lib_contents = insert_sorted_line(
+            &read(&lib)?,
+            year,
+            &format!("pub mod y{};", year),
+            module_year,
+        )
+        .with_context(|| format!("could not add {} to {}", year, lib.display()))?;
+        updates.push((lib, lib_contents));
+    } else {
+        let year_contents = insert_sorted_line(
+            &read(&year_module)?,
+            day,
+            &format!("pub mod day{};", day),
+            module_day,
+        )
+        .with_context(|| format!("could not add day {} to {}", day, year_module.display()))?;
+        updates.push((year_module.clone(), year_contents));
+    }
+    let registry_contents = insert_sorted_line(
+        &read(&registry)?,
+        (year, day),
+        &format!("    Entry::of::<crate::y{0}::day{1}::Day{1}>(),", year, day),
+        registry_day,
+    )
+    .with_context(|| {
+        format!(
+            "could not add {} day {} to {}",
+            year,
+            day,
+            registry.display()
+        )
+    })?;
+    updates.push((registry, registry_contents));
+
+    let mut scaffolded = Scaffolded {
+        created: Vec::new(),
+        updated: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    std::fs::create_dir_all(&year_dir)
+        .with_context(|| format!("could not create {}", year_dir.display()))?;
+    if new_year {
+        create_new(
+            &year_module,
+            &format!(
+                "//! Solutions to the [Advent of Code {0}](https://adventofcode.com/{0}) \
+                 puzzles\n\npub mod day{1};\n",
+                year, day
+            ),
+        )?;
+        scaffolded.created.push(year_module);
+    }
+    let contents = TEMPLATE
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string());
+    create_new(&module, &contents)?;
+    scaffolded.created.push(module);
+    for (path, contents) in updates {
+        std::fs::write(&path, contents)
+            .with_context(|| format!("could not write {}", path.display()))?;
+        scaffolded.updated.push(path);
+    }
+    if input.exists() {
+        scaffolded.skipped.push(input);
+    } else {
+        if let Some(dir) = input.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("could not create {}", dir.display()))?;
+        }
+        create_new(&input, "")?;
+        scaffolded.created.push(input);
+    }
+
+    Ok(scaffolded)
+}
+
+/// Gets the day from a line like `pub mod day7;`
+fn module_day(line: &str) -> Option<u16> {
+    line.strip_prefix("pub mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// Gets the year from a line like `pub mod y2022;`
+fn module_year(line: &str) -> Option<u16> {
+    line.strip_prefix("pub mod y")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// Gets the year and day from a line like `    Entry::of::<crate::y2022::day7::Day7>(),`
+fn registry_day(line: &str) -> Option<(u16, u16)> {
+    let rest = line.trim_start().strip_prefix("Entry::of::<crate::y")?;
+    let (year, rest) = rest.split_once("::day")?;
+    let (day, _) = rest.split_once("::")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))
+}
+
+/// Writes a file, failing if it already exists
+fn create_new(path: &Path, contents: &str) -> Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .with_context(|| format!("could not create {}", path.display()))
+}
+
+/// Inserts `new_line` into a list of lines, such as one for each day, keeping the list in
+/// order of `key`. `key_of` gets the key from a line, if it is part of the list.
+fn insert_sorted_line<K: Ord>(
+    contents: &str,
+    key: K,
+    new_line: &str,
+    key_of: fn(&str) -> Option<K>,
+) -> Result<String> {
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let keys: Vec<(usize, K)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, key_of(line)?)))
+        .collect();
+    if keys.iter().any(|(_, k)| *k == key) {
+        bail!("it is already there");
+    }
+    let Some(&(last, _)) = keys.last() else {
+        bail!("could not find where the list is");
+    };
+    let insert_at = keys
+        .iter()
+        .find(|(_, k)| *k > key)
+        .map_or(last + 1, |(i, _)| *i);
+    lines.insert(insert_at, new_line);
+
+    let mut res = lines.join("\n");
+    if contents.ends_with('\n') {
+        res.push('\n');
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_inserts_new_day_after_existing_days() {
+        let contents = "pub mod day1;\npub mod day2;\npub mod input;\n";
+        let res = insert_sorted_line(contents, 10, "pub mod day10;", module_day).unwrap();
+        assert_eq!(
+            res,
+            "pub mod day1;\npub mod day2;\npub mod day10;\npub mod input;\n"
+        );
+    }
+
+    #[test]
+    fn test_inserts_new_day_in_order() {
+        let contents = "pub mod day1;\npub mod day3;";
+        let res = insert_sorted_line(contents, 2, "pub mod day2;", module_day).unwrap();
+        assert_eq!(res, "pub mod day1;\npub mod day2;\npub mod day3;");
+    }
+
+    #[test]
+    fn test_will_not_add_a_day_twice() {
+        let contents = "pub mod day1;\npub mod day2;\n";
+        assert!(insert_sorted_line(contents, 2, "pub mod day2;", module_day).is_err());
+    }
+
+    #[test]
+    fn test_registry_is_kept_in_year_and_day_order() {
+        let contents = "    Entry::of::<crate::y2022::day1::Day1>(),
+    Entry::of::<crate::y2022::day8::Day8>().streaming::<crate::y2022::day8::Day8>(),
+];";
+        let line = "    Entry::of::<crate::y2021::day9::Day9>(),";
+        let res = insert_sorted_line(contents, (2021, 9), line, registry_day).unwrap();
+        assert!(res.starts_with(line));
+        let line = "    Entry::of::<crate::y2022::day2::Day2>(),";
+        let res = insert_sorted_line(contents, (2022, 2), line, registry_day).unwrap();
+        assert_eq!(res.lines().nth(1), Some(line));
+        assert!(insert_sorted_line(contents, (2022, 8), line, registry_day).is_err());
+    }
+
+    #[test]
+    fn test_can_scaffold_a_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/y2022")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub mod puzzle;\npub mod y2022;\n").unwrap();
+        std::fs::write(root.join("src/y2022/mod.rs"), "//! 2022\n\npub mod day1;\n").unwrap();
+        std::fs::write(
+            root.join("src/registry.rs"),
+            "static SOLUTIONS: &[Entry] = &[\n    Entry::of::<crate::y2022::day1::Day1>(),\n];\n",
+        )
+        .unwrap();
+
+        let scaffolded = new_day(&root, 2022, 2).unwrap();
+        assert_eq!(scaffolded.created.len(), 2);
+        let module = read(&root.join("src/y2022/day2.rs")).unwrap();
+        assert!(module.contains("impl Solution for Day2 {"));
+        assert!(module.contains("const YEAR: u16 = 2022;"));
+        assert_eq!(
+            read(&root.join("src/y2022/mod.rs")).unwrap(),
+            "//! 2022\n\npub mod day1;\npub mod day2;\n"
+        );
+        assert!(read(&root.join("src/registry.rs"))
+            .unwrap()
+            .contains("y2022::day1::Day1>(),\n    Entry::of::<crate::y2022::day2::Day2>(),\n];"));
+        assert!(root.join("input/2022/day2.txt").exists());
+
+        // Running it again must not overwrite what was just created
+        assert!(new_day(&root, 2022, 2).is_err());
+
+        // The first day of a year adds a module for the year
+        let scaffolded = new_day(&root, 2023, 1).unwrap();
+        assert_eq!(scaffolded.created.len(), 3);
+        assert!(read(&root.join("src/y2023/mod.rs"))
+            .unwrap()
+            .ends_with("\n\npub mod day1;\n"));
+        assert_eq!(
+            read(&root.join("src/lib.rs")).unwrap(),
+            "pub mod puzzle;\npub mod y2022;\npub mod y2023;\n"
+        );
+        assert!(read(&root.join("src/registry.rs"))
+            .unwrap()
+            .contains("y2022::day2::Day2>(),\n    Entry::of::<crate::y2023::day1::Day1>(),\n];"));
+        assert!(root.join("input/2023/day1.txt").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+use std::io::BufRead;
+
+use anyhow::Result;
+
+use crate::answer::Answer;
+
+/// A solution to both parts of one day's puzzle. Implementations are listed in
+/// [`crate::registry`] so that the runner can find them.
+pub trait Solution {
+    /// The year of the event that the puzzle is from
+    const YEAR: u16;
+    /// The day of the advent calendar that this solves
+    const DAY: u16;
+    /// The puzzle's title, as given on the website
+    const TITLE: &'static str;
+    /// The examples given in the puzzle description
+    const EXAMPLES: &'static [Example];
+
+    /// The puzzle input, after it has been parsed from the raw input text
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// A solution that can also read its input as it goes, rather than needing all of it in
+/// memory at once. Register it with [`crate::registry::Entry::streaming`].
+pub trait Streaming: Solution {
+    fn part1_from(input: &mut dyn BufRead) -> Result<Answer>;
+
+    fn part2_from(input: &mut dyn BufRead) -> Result<Answer>;
+}
+
+/// An example input from a puzzle description, along with the answers that the description
+/// gives for it
+#[derive(Debug)]
+pub struct Example {
+    pub input: &'static str,
+    /// The expected answer to part 1, if the description gives one for this input
+    pub part1: Option<&'static str>,
+    /// The expected answer to part 2, if the description gives one for this input
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    /// The expected answer to `part`, if there is one
+    pub fn answer(&self, part: u16) -> Option<Answer> {
+        let answer = match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        };
+        answer.map(Answer::infer)
+    }
+}
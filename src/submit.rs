@@ -0,0 +1,433 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::puzzle::Puzzle;
+
+/// What the website said about a submitted answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "verdict")]
+pub enum Verdict {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+        /// How long to wait before submitting another answer
+        wait_secs: Option<u64>,
+    },
+    /// An answer was submitted too recently, so this one was not checked
+    TooRecent {
+        wait_secs: Option<u64>,
+    },
+    /// The puzzle has already been solved, or its first part has not been
+    AlreadySolved,
+    /// A response that could not be understood, with the text of the response
+    Unknown {
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// Works out the verdict from the HTML page returned after submitting an answer
+    pub fn from_response(html: &str) -> Self {
+        let message = main_text(html);
+        if message.contains("That's the right answer") {
+            Self::Correct
+        } else if message.contains("That's not the right answer") {
+            let hint = if message.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if message.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Self::Incorrect {
+                hint,
+                wait_secs: wait_secs(&message),
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Self::TooRecent {
+                wait_secs: wait_secs(&message),
+            }
+        } else if message.contains("Did you already complete it?") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown { message }
+        }
+    }
+
+    fn wait_secs(&self) -> Option<u64> {
+        match self {
+            Self::Incorrect { wait_secs, .. } | Self::TooRecent { wait_secs } => *wait_secs,
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect { hint, .. } => match hint {
+                Some(Hint::TooHigh) => write!(f, "incorrect, too high"),
+                Some(Hint::TooLow) => write!(f, "incorrect, too low"),
+                None => write!(f, "incorrect"),
+            },
+            Self::TooRecent { .. } => write!(f, "not checked, an answer was given too recently"),
+            Self::AlreadySolved => write!(f, "not checked, the puzzle is not being solved"),
+            Self::Unknown { message } => write!(f, "unknown response: {}", message),
+        }?;
+        if let Some(wait) = self.wait_secs() {
+            write!(f, " (wait {}s before trying again)", wait)?;
+        }
+        Ok(())
+    }
+}
+
+/// Gets the text of the page's `<main>` element, without any tags
+fn main_text(html: &str) -> String {
+    let main = html
+        .split_once("<main>")
+        .and_then(|(_, rest)| rest.split_once("</main>"))
+        .map_or(html, |(main, _)| main);
+    let mut text = String::with_capacity(main.len());
+    let mut in_tag = false;
+    for c in main.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Finds how long the website says to wait in messages like "You have 1m 5s left to wait"
+/// or "please wait 5 minutes before trying again"
+fn wait_secs(message: &str) -> Option<u64> {
+    if let Some((_, rest)) = message.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        return left.split_whitespace().try_fold(0, |secs, part| {
+            let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let n: u64 = n.parse().ok()?;
+            match unit {
+                "h" => Some(secs + n * 3600),
+                "m" => Some(secs + n * 60),
+                "s" => Some(secs + n),
+                _ => None,
+            }
+        });
+    }
+    let (_, rest) = message.split_once("wait ")?;
+    let (amount, _) = rest.split_once(" before trying again")?;
+    match amount.split_once(' ')? {
+        ("one", "minute") => Some(60),
+        (n, "minutes") => Some(n.parse::<u64>().ok()? * 60),
+        _ => None,
+    }
+}
+
+/// A previous submission
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u16,
+    pub part: u16,
+    pub answer: String,
+    /// When the answer was submitted, in seconds since the Unix epoch
+    pub submitted_at: u64,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+/// Every answer that has been submitted, stored as one JSON object per line
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Loads the history from `path`. A missing file is treated as an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        let attempts = match std::fs::read_to_string(path) {
+            Ok(contents) => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(i, line)| {
+                    serde_json::from_str(line).with_context(|| {
+                        format!("could not parse line {} of {}", i + 1, path.display())
+                    })
+                })
+                .collect::<Result<_>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e).with_context(|| format!("could not read {}", path.display())),
+        };
+        Ok(Self {
+            path: path.to_owned(),
+            attempts,
+        })
+    }
+
+    /// Fails if submitting `answer` is pointless: because the puzzle has already been
+    /// solved, the answer is already known to be wrong, or the website has asked for
+    /// answers not to be submitted yet
+    pub fn check_can_submit(&self, puzzle: Puzzle, answer: &str, now: u64) -> Result<()> {
+        let attempts = || {
+            self.attempts.iter().filter(move |a| {
+                a.year == puzzle.year() && a.day == puzzle.day() && a.part == puzzle.part()
+            })
+        };
+
+        if let Some(correct) = attempts().find(|a| a.verdict == Verdict::Correct) {
+            bail!("{} has already been solved with {}", puzzle, correct.answer);
+        }
+        for attempt in attempts() {
+            let Verdict::Incorrect { hint, .. } = attempt.verdict else {
+                continue;
+            };
+            if attempt.answer == answer {
+                bail!(
+                    "{} was already submitted and is not the right answer",
+                    answer
+                );
+            }
+            let (Ok(wrong), Ok(answer)) = (attempt.answer.parse::<i64>(), answer.parse::<i64>())
+            else {
+                continue;
+            };
+            match hint {
+                Some(Hint::TooHigh) if answer >= wrong => {
+                    bail!("{} is too high, as {} was already too high", answer, wrong)
+                }
+                Some(Hint::TooLow) if answer <= wrong => {
+                    bail!("{} is too low, as {} was already too low", answer, wrong)
+                }
+                _ => {}
+            }
+        }
+
+        let wait_until = self
+            .attempts
+            .iter()
+            .filter_map(|a| Some(a.submitted_at + a.verdict.wait_secs()?))
+            .max();
+        if let Some(wait_until) = wait_until.filter(|wait_until| *wait_until > now) {
+            bail!(
+                "answers cannot be submitted for another {}s",
+                wait_until - now
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds an attempt to the history file
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        let mut line = serde_json::to_string(&attempt)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .with_context(|| format!("could not write to {}", self.path.display()))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+/// The current time in seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><header>Advent of Code</header>\
+             <main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    fn puzzle(s: &str) -> Puzzle {
+        Puzzle::try_from(s).unwrap()
+    }
+
+    fn attempt(answer: &str, submitted_at: u64, verdict: Verdict) -> Attempt {
+        Attempt {
+            year: 2022,
+            day: 1,
+            part: 1,
+            answer: answer.into(),
+            submitted_at,
+            verdict,
+        }
+    }
+
+    fn history(attempts: Vec<Attempt>) -> History {
+        History {
+            path: "submissions.jsonl".into(),
+            attempts,
+        }
+    }
+
+    #[test]
+    fn test_can_parse_correct_answer() {
+        let html = page(
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> \
+             closer to collecting enough star fruit.",
+        );
+        assert_eq!(Verdict::from_response(&html), Verdict::Correct);
+    }
+
+    #[test]
+    fn test_can_parse_incorrect_answers() {
+        let html = page(
+            "That's not the right answer; your answer is too high.  If you're stuck, make sure \
+             you're using the full input data.  Please wait one minute before trying again.",
+        );
+        assert_eq!(
+            Verdict::from_response(&html),
+            Verdict::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait_secs: Some(60)
+            }
+        );
+        let html = page(
+            "That's not the right answer; your answer is too low.  Please wait 5 minutes before \
+             trying again.",
+        );
+        assert_eq!(
+            Verdict::from_response(&html),
+            Verdict::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait_secs: Some(300)
+            }
+        );
+        let html =
+            page("That's not the right answer.  Please wait one minute before trying again.");
+        assert_eq!(
+            Verdict::from_response(&html),
+            Verdict::Incorrect {
+                hint: None,
+                wait_secs: Some(60)
+            }
+        );
+    }
+
+    #[test]
+    fn test_can_parse_rate_limit() {
+        let html = page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 1m 5s left to wait.",
+        );
+        assert_eq!(
+            Verdict::from_response(&html),
+            Verdict::TooRecent {
+                wait_secs: Some(65)
+            }
+        );
+    }
+
+    #[test]
+    fn test_can_parse_already_solved() {
+        let html =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(Verdict::from_response(&html), Verdict::AlreadySolved);
+    }
+
+    #[test]
+    fn test_refuses_known_wrong_answers() {
+        let history = history(vec![attempt(
+            "500",
+            0,
+            Verdict::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait_secs: Some(60),
+            },
+        )]);
+        assert!(history.check_can_submit(puzzle("1-1"), "500", 100).is_err());
+        assert!(history.check_can_submit(puzzle("1-1"), "600", 100).is_err());
+        assert!(history.check_can_submit(puzzle("1-1"), "400", 100).is_ok());
+        assert!(history.check_can_submit(puzzle("1-2"), "500", 100).is_ok());
+    }
+
+    #[test]
+    fn test_refuses_to_submit_while_waiting() {
+        let history = history(vec![attempt(
+            "500",
+            1000,
+            Verdict::Incorrect {
+                hint: None,
+                wait_secs: Some(60),
+            },
+        )]);
+        let err = history
+            .check_can_submit(puzzle("1-1"), "400", 1030)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "answers cannot be submitted for another 30s"
+        );
+        assert!(history.check_can_submit(puzzle("1-1"), "400", 1060).is_ok());
+    }
+
+    #[test]
+    fn test_refuses_solved_puzzles() {
+        let history = history(vec![attempt("24000", 0, Verdict::Correct)]);
+        assert!(history
+            .check_can_submit(puzzle("1-1"), "24000", 100)
+            .is_err());
+        assert!(history
+            .check_can_submit(puzzle("2022-1-2"), "24000", 100)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_attempts_are_stored_as_json_lines() {
+        let path = std::env::temp_dir().join(format!("aoc-submit-{}.jsonl", std::process::id()));
+        let mut history = History::load(&path).unwrap();
+        history
+            .record(attempt(
+                "500",
+                1000,
+                Verdict::Incorrect {
+                    hint: Some(Hint::TooLow),
+                    wait_secs: None,
+                },
+            ))
+            .unwrap();
+        history
+            .record(attempt("24000", 1100, Verdict::Correct))
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"year\":2022,\"day\":1,\"part\":1,\"answer\":\"500\",\"submitted_at\":1000,\
+             \"verdict\":\"incorrect\",\"hint\":\"too_low\",\"wait_secs\":null}\n\
+             {\"year\":2022,\"day\":1,\"part\":1,\"answer\":\"24000\",\"submitted_at\":1100,\
+             \"verdict\":\"correct\"}\n"
+        );
+        assert_eq!(History::load(&path).unwrap().attempts, history.attempts);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Calls `run` straight away, and then again every time one of `paths` changes. Never
+/// returns, so must be stopped with Ctrl+C.
+pub fn watch(paths: &[PathBuf], mut run: impl FnMut()) -> ! {
+    let mut last = snapshot(paths);
+    run();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = snapshot(paths);
+        if current != last {
+            last = current;
+            run();
+        }
+    }
+}
+
+/// Clears the terminal and moves the cursor to the top left
+pub fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+}
+
+/// The modification time and size of each file, or `None` for files that cannot be read.
+/// The size is included as well as the time as some filesystems only store times to the
+/// nearest second.
+fn snapshot(paths: &[PathBuf]) -> Vec<Option<(SystemTime, u64)>> {
+    paths
+        .iter()
+        .map(|path| {
+            let metadata = std::fs::metadata(path).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_changes_when_a_file_does() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let paths = vec![path.clone()];
+        assert_eq!(snapshot(&paths), vec![None]);
+
+        std::fs::write(&path, "1000").unwrap();
+        let created = snapshot(&paths);
+        assert!(created[0].is_some());
+        assert_eq!(snapshot(&paths), created);
+
+        std::fs::write(&path, "1000\n2000").unwrap();
+        assert_ne!(snapshot(&paths), created);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
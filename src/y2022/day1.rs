@@ -0,0 +1,238 @@
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
+use nom::{
+    character::complete::digit1,
+    combinator::{map_res, opt},
+    IResult,
+};
+
+use crate::answer::Answer;
+use crate::parse;
+use crate::solution::{Example, Solution, Streaming};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("24000"),
+        part2: Some("45000"),
+    }];
+
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>> {
+        elf_calories(input)
+    }
+
+    fn part1(input: &Vec<usize>) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Vec<usize>) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+impl Streaming for Day1 {
+    fn part1_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part1_from(input)?.into())
+    }
+
+    fn part2_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part2_from(input)?.into())
+    }
+}
+
+/// The most calories carried by one elf, given the total carried by each elf
+pub fn part1(elf_calories: &[usize]) -> Result<usize> {
+    let highest_calories = elf_calories.iter().max().context("no elves were listed")?;
+
+    Ok(*highest_calories)
+}
+
+/// The calories carried by the three elves carrying the most, given the total carried by
+/// each elf
+pub fn part2(elf_calories: &[usize]) -> Result<usize> {
+    let mut elf_calories = elf_calories.to_vec();
+    elf_calories.sort();
+    elf_calories.reverse();
+    let top3_elves_sum = elf_calories
+        .iter()
+        .take(3)
+        .try_fold(0_usize, |sum, calories| sum.checked_add(*calories))
+        .context("the top three elves are carrying too many calories to add up")?;
+
+    Ok(top3_elves_sum)
+}
+
+/// Part 1, reading the input a line at a time
+pub fn part1_from(input: impl BufRead) -> Result<usize> {
+    part1(&read_top_elf_calories(input, 1)?)
+}
+
+/// Part 2, reading the input a line at a time
+pub fn part2_from(input: impl BufRead) -> Result<usize> {
+    part2(&read_top_elf_calories(input, 3)?)
+}
+
+const CALORIES: &str = "a number of calories or a blank line";
+
+/// The total calories carried by each elf. Each elf's items are listed one per line, with a
+/// blank line between elves.
+pub fn elf_calories(input: &str) -> Result<Vec<usize>> {
+    let mut totals = ElfTotals::default();
+    let mut elf_calories = Vec::new();
+    for item in parse::lines(input, CALORIES, calories)? {
+        elf_calories.extend(totals.add(item)?);
+    }
+    elf_calories.extend(totals.add(None)?);
+    Ok(elf_calories)
+}
+
+/// The `count` highest totals of calories carried by an elf, keeping only those in memory
+fn read_top_elf_calories(input: impl BufRead, count: usize) -> Result<Vec<usize>> {
+    let mut totals = ElfTotals::default();
+    let mut top = Vec::with_capacity(count + 1);
+    let mut keep = |total: Option<usize>| {
+        if let Some(total) = total {
+            let i = top.partition_point(|other| *other >= total);
+            top.insert(i, total);
+            top.truncate(count);
+        }
+    };
+    parse::each_line(input, CALORIES, |line, number| {
+        let item = parse::line(line, number, CALORIES, calories)?;
+        keep(totals.add(item)?);
+        Ok(())
+    })?;
+    keep(totals.add(None)?);
+    Ok(top)
+}
+
+/// Adds up the calories carried by each elf, one item at a time
+#[derive(Default)]
+struct ElfTotals {
+    current: Option<usize>,
+    elves: usize,
+}
+
+impl ElfTotals {
+    /// Adds the next line's item, where `None` is a blank line. Returns the elf's total when
+    /// that finishes an elf's items.
+    fn add(&mut self, item: Option<usize>) -> Result<Option<usize>> {
+        let Some(calories) = item else {
+            if self.current.is_some() {
+                self.elves += 1;
+            }
+            return Ok(self.current.take());
+        };
+        let total = self
+            .current
+            .unwrap_or_default()
+            .checked_add(calories)
+            .with_context(|| {
+                format!(
+                    "elf {} is carrying too many calories to add up",
+                    self.elves + 1
+                )
+            })?;
+        self.current = Some(total);
+        Ok(None)
+    }
+}
+
+fn calories(i: &str) -> IResult<&str, Option<usize>> {
+    opt(map_res(digit1, |n: &str| n.parse::<usize>()))(i)
+}
+
+/// The example input from the puzzle description
+const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        let res = part1(&elf_calories(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(res, 24000);
+    }
+
+    #[test]
+    fn test_part2_gives_correct_answer() {
+        let res = part2(&elf_calories(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(res, 45000);
+    }
+
+    #[test]
+    fn test_reports_where_calories_are_not_numbers() {
+        let err = elf_calories("1000\n2000\n\n30x0\n").unwrap_err();
+        let err = err.downcast::<parse::ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (4, 3));
+    }
+
+    #[test]
+    fn test_reports_totals_that_are_too_large() {
+        let input = format!("{}\n1\n\n1", usize::MAX);
+        assert_eq!(
+            elf_calories(&input).unwrap_err().to_string(),
+            "elf 1 is carrying too many calories to add up"
+        );
+        let input = format!("{}\n\n1", usize::MAX);
+        assert!(part2(&elf_calories(&input).unwrap()).is_err());
+        assert!(elf_calories("\n\n").is_err());
+        assert!(part1(&[]).is_err());
+    }
+
+    /// Renders the calories carried by each elf as puzzle input
+    fn calorie_groups() -> impl Strategy<Value = (Vec<Vec<usize>>, String)> {
+        prop::collection::vec(prop::collection::vec(0..100_000_usize, 1..10), 1..20).prop_map(
+            |elves| {
+                let input = elves
+                    .iter()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .map(|item| item.to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                (elves, input)
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_answers_match_sorting_every_total((elves, input) in calorie_groups()) {
+            let mut totals: Vec<usize> = elves.iter().map(|items| items.iter().sum()).collect();
+            totals.sort_unstable_by(|a, b| b.cmp(a));
+            let elf_calories = elf_calories(&input).unwrap();
+            prop_assert_eq!(part1(&elf_calories).unwrap(), totals[0]);
+            prop_assert_eq!(part2(&elf_calories).unwrap(), totals.iter().take(3).sum::<usize>());
+        }
+    }
+}
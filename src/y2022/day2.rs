@@ -0,0 +1,434 @@
+use std::io::BufRead;
+
+use anyhow::Result;
+use nom::character::complete::{one_of, space1};
+use nom::combinator::{map, map_res};
+use nom::sequence::separated_pair;
+use nom::IResult;
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::{Example, Solution, Streaming};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("15"),
+        part2: Some("12"),
+    }];
+
+    type Input = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Vec<Round>> {
+        Ok(parse_rounds(input)?)
+    }
+
+    fn part1(input: &Vec<Round>) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Vec<Round>) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+impl Streaming for Day2 {
+    fn part1_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part1_from(input)?.into())
+    }
+
+    fn part2_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part2_from(input)?.into())
+    }
+}
+
+/// The total score when the second column is the shape to respond with
+pub fn part1(rounds: &[Round]) -> usize {
+    rounds.iter().map(|round| round.part1_play().score()).sum()
+}
+
+/// The total score when the second column is how the round needs to end
+pub fn part2(rounds: &[Round]) -> usize {
+    rounds.iter().map(|round| round.part2_play().score()).sum()
+}
+
+/// Part 1, reading the input a line at a time
+pub fn part1_from(input: impl BufRead) -> Result<usize> {
+    read_total_score(input, PART1_PLAY, parse_part1_play)
+}
+
+/// Part 2, reading the input a line at a time
+pub fn part2_from(input: impl BufRead) -> Result<usize> {
+    read_total_score(input, PART2_PLAY, parse_part2_play)
+}
+
+fn read_total_score(
+    input: impl BufRead,
+    expected: &str,
+    mut parser: impl FnMut(&str) -> IResult<&str, Play>,
+) -> Result<usize> {
+    let mut total_score = 0;
+    parse::each_line(input, expected, |line, number| {
+        total_score += parse::line(line, number, expected, &mut parser)?.score();
+        Ok(())
+    })?;
+    Ok(total_score)
+}
+
+/// A line of the strategy guide
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Round {
+    pub opponent_shape: Shape,
+    pub instruction: Instruction,
+}
+
+impl Round {
+    /// The play when the instruction is the shape to respond with
+    pub fn part1_play(&self) -> Play {
+        Play {
+            opponent_shape: self.opponent_shape,
+            response: self.instruction.shape(),
+        }
+    }
+
+    /// The play when the instruction is how the round needs to end
+    pub fn part2_play(&self) -> Play {
+        Play {
+            opponent_shape: self.opponent_shape,
+            response: self.instruction.result().get_response(self.opponent_shape),
+        }
+    }
+}
+
+/// The second column of the strategy guide, which the two parts read differently
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instruction {
+    X,
+    Y,
+    Z,
+}
+
+impl Instruction {
+    /// The shape that this means in part 1
+    pub fn shape(&self) -> Shape {
+        match self {
+            Self::X => Shape::Rock,
+            Self::Y => Shape::Paper,
+            Self::Z => Shape::Scissors,
+        }
+    }
+
+    /// The result that this means in part 2
+    pub fn result(&self) -> DesiredResult {
+        match self {
+            Self::X => DesiredResult::Lose,
+            Self::Y => DesiredResult::Draw,
+            Self::Z => DesiredResult::Win,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Play {
+    pub opponent_shape: Shape,
+    pub response: Shape,
+}
+
+impl Play {
+    pub fn score(&self) -> usize {
+        self.outcome_score() + self.response.score()
+    }
+
+    /// Score for just whether this was a win/lose/draw
+    fn outcome_score(&self) -> usize {
+        // Draw
+        if self.opponent_shape == self.response {
+            return 3;
+        }
+        match (self.opponent_shape, self.response) {
+            // Win
+            (Shape::Rock, Shape::Paper) => 6,
+            (Shape::Paper, Shape::Scissors) => 6,
+            (Shape::Scissors, Shape::Rock) => 6,
+            // Loss
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    pub fn score(&self) -> usize {
+        match self {
+            Self::Rock => 1,
+            Self::Paper => 2,
+            Self::Scissors => 3,
+        }
+    }
+}
+
+impl TryFrom<char> for Shape {
+    type Error = String;
+
+    fn try_from(input: char) -> Result<Self, Self::Error> {
+        match input {
+            'A' | 'X' => Ok(Shape::Rock),
+            'B' | 'Y' => Ok(Shape::Paper),
+            'C' | 'Z' => Ok(Shape::Scissors),
+            _ => Err(format!("cannot convert {} to a Rock/Paper/Scissors", input)),
+        }
+    }
+}
+
+pub enum DesiredResult {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl DesiredResult {
+    pub fn get_response(&self, opponent_shape: Shape) -> Shape {
+        match (self, opponent_shape) {
+            (Self::Lose, Shape::Rock) => Shape::Scissors,
+            (Self::Lose, Shape::Paper) => Shape::Rock,
+            (Self::Lose, Shape::Scissors) => Shape::Paper,
+            (Self::Draw, Shape::Rock) => Shape::Rock,
+            (Self::Draw, Shape::Paper) => Shape::Paper,
+            (Self::Draw, Shape::Scissors) => Shape::Scissors,
+            (Self::Win, Shape::Rock) => Shape::Paper,
+            (Self::Win, Shape::Paper) => Shape::Scissors,
+            (Self::Win, Shape::Scissors) => Shape::Rock,
+        }
+    }
+}
+
+impl TryFrom<char> for DesiredResult {
+    type Error = String;
+
+    fn try_from(input: char) -> Result<Self, Self::Error> {
+        match input {
+            'X' => Ok(DesiredResult::Lose),
+            'Y' => Ok(DesiredResult::Draw),
+            'Z' => Ok(DesiredResult::Win),
+            _ => Err(format!("cannot convert {} to a Win/Lose/Draw", input)),
+        }
+    }
+}
+
+fn opponent_shape(input: &str) -> IResult<&str, Shape> {
+    map_res(one_of("ABC"), Shape::try_from)(input)
+}
+
+fn instruction(input: &str) -> IResult<&str, Instruction> {
+    map(one_of("XYZ"), |c| match c {
+        'X' => Instruction::X,
+        'Y' => Instruction::Y,
+        _ => Instruction::Z,
+    })(input)
+}
+
+const ROUND: &str = "a shape and an instruction like 'A Y'";
+const PART1_PLAY: &str = "two shapes like 'A Y'";
+const PART2_PLAY: &str = "a shape and a result like 'A Y'";
+
+pub fn parse_round(input: &str) -> IResult<&str, Round> {
+    map(
+        separated_pair(opponent_shape, space1, instruction),
+        |(opponent_shape, instruction)| Round {
+            opponent_shape,
+            instruction,
+        },
+    )(input)
+}
+
+pub fn parse_rounds(input: &str) -> Result<Vec<Round>, ParseError> {
+    parse::lines(input, ROUND, parse_round)
+}
+
+pub fn parse_part1_play(input: &str) -> IResult<&str, Play> {
+    map(parse_round, |round| round.part1_play())(input)
+}
+
+pub fn parse_part1_plays(input: &str) -> Result<Vec<Play>, ParseError> {
+    parse::lines(input, PART1_PLAY, parse_part1_play)
+}
+
+pub fn parse_part2_play(input: &str) -> IResult<&str, Play> {
+    map(parse_round, |round| round.part2_play())(input)
+}
+
+pub fn parse_part2_plays(input: &str) -> Result<Vec<Play>, ParseError> {
+    parse::lines(input, PART2_PLAY, parse_part2_play)
+}
+
+/// The example input from the puzzle description
+const EXAMPLE: &str = "A Y\nB X\nC Z";
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_can_parse_a_single_line_for_part1() {
+        let input = "A Y";
+        assert_eq!(
+            parse_part1_play(input),
+            Ok((
+                "",
+                Play {
+                    opponent_shape: Shape::Rock,
+                    response: Shape::Paper,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn can_parse_multiple_lines_of_input_for_part1() {
+        assert_eq!(
+            parse_part1_plays(EXAMPLE),
+            Ok(vec![
+                Play {
+                    opponent_shape: Shape::Rock,
+                    response: Shape::Paper,
+                },
+                Play {
+                    opponent_shape: Shape::Paper,
+                    response: Shape::Rock,
+                },
+                Play {
+                    opponent_shape: Shape::Scissors,
+                    response: Shape::Scissors,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        let res = part1(&parse_rounds(EXAMPLE).unwrap());
+        assert_eq!(res, 15);
+    }
+
+    #[test]
+    fn test_can_parse_a_single_line_for_part2() {
+        let input = "A Y";
+        assert_eq!(
+            parse_part2_play(input),
+            Ok((
+                "",
+                Play {
+                    opponent_shape: Shape::Rock,
+                    response: Shape::Rock,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn can_parse_multiple_lines_of_input_for_part2() {
+        assert_eq!(
+            parse_part2_plays(EXAMPLE),
+            Ok(vec![
+                Play {
+                    opponent_shape: Shape::Rock,
+                    response: Shape::Rock,
+                },
+                Play {
+                    opponent_shape: Shape::Paper,
+                    response: Shape::Rock,
+                },
+                Play {
+                    opponent_shape: Shape::Scissors,
+                    response: Shape::Rock,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_part2_gives_correct_answer() {
+        let res = part2(&parse_rounds(EXAMPLE).unwrap());
+        assert_eq!(res, 12);
+    }
+
+    #[test]
+    fn test_reports_where_a_play_is_malformed() {
+        let err = parse_part1_plays("A Y\nB Q\nC Z").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "B Q");
+        let err = parse_rounds("A Y\nB Q\nC Z").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    /// The score for a round, written out in full rather than worked out
+    fn reference_score(opponent: char, response: char) -> usize {
+        match (opponent, response) {
+            ('A', 'X') => 1 + 3,
+            ('A', 'Y') => 2 + 6,
+            ('A', 'Z') => 3,
+            ('B', 'X') => 1,
+            ('B', 'Y') => 2 + 3,
+            ('B', 'Z') => 3 + 6,
+            ('C', 'X') => 1 + 6,
+            ('C', 'Y') => 2,
+            ('C', 'Z') => 3 + 3,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The response that gets the result asked for in part 2
+    fn reference_response(opponent: char, result: char) -> char {
+        match (opponent, result) {
+            ('A', 'X') | ('B', 'Z') | ('C', 'Y') => 'Z',
+            ('A', 'Y') | ('B', 'X') | ('C', 'Z') => 'X',
+            _ => 'Y',
+        }
+    }
+
+    fn rounds() -> impl Strategy<Value = Vec<(char, char)>> {
+        prop::collection::vec(
+            (
+                prop::sample::select(vec!['A', 'B', 'C']),
+                prop::sample::select(vec!['X', 'Y', 'Z']),
+            ),
+            1..100,
+        )
+    }
+
+    fn render(rounds: &[(char, char)]) -> String {
+        rounds
+            .iter()
+            .map(|(opponent, second)| format!("{} {}\n", opponent, second))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_scores_match_score_table(rounds in rounds()) {
+            let input = render(&rounds);
+            let part1_score = rounds.iter().map(|(o, r)| reference_score(*o, *r)).sum::<usize>();
+            let part2_score = rounds
+                .iter()
+                .map(|(o, result)| reference_score(*o, reference_response(*o, *result)))
+                .sum::<usize>();
+            let parsed = parse_rounds(&input).unwrap();
+            prop_assert_eq!(part1(&parsed), part1_score);
+            prop_assert_eq!(part2(&parsed), part2_score);
+        }
+    }
+}
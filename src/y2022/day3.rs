@@ -0,0 +1,276 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use anyhow::{bail, Result};
+use nom::{character::complete::alpha1, combinator::verify, IResult};
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::{Example, Solution, Streaming};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("157"),
+        part2: Some("70"),
+    }];
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        let rucksacks = parse_rucksacks(input)?;
+        Ok(rucksacks.into_iter().map(str::to_owned).collect())
+    }
+
+    fn part1(input: &Vec<String>) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Vec<String>) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+impl Streaming for Day3 {
+    fn part1_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part1_from(input)?.into())
+    }
+
+    fn part2_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part2_from(input)?.into())
+    }
+}
+
+/// The sum of the priorities of the item type in both compartments of each rucksack. Errors
+/// point at the line of the input that each rucksack came from.
+pub fn part1(rucksacks: &[impl AsRef<str>]) -> Result<usize> {
+    let mut sum = 0;
+    for (i, rucksack) in rucksacks.iter().enumerate() {
+        let rucksack = rucksack.as_ref();
+        // According to the instructions, there will always be one item type common to both
+        // compartments.
+        let item = in_both_compartments(rucksack)
+            .ok_or_else(|| ParseError::line(rucksack, 1, IN_BOTH_COMPARTMENTS).offset_lines(i))?;
+        sum += priority(item)?;
+    }
+    Ok(sum)
+}
+
+/// The sum of the priorities of the badge carried by each group of three rucksacks
+pub fn part2(rucksacks: &[impl AsRef<str>]) -> Result<usize> {
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(ParseError::line("", 1, ANOTHER_RUCKSACK)
+            .offset_lines(rucksacks.len())
+            .into());
+    }
+
+    let mut sum: usize = 0;
+    for (i, group) in rucksacks.chunks(3).enumerate() {
+        let badge = in_every_rucksack(group).ok_or_else(|| {
+            ParseError::line(group[0].as_ref(), 1, IN_EVERY_RUCKSACK).offset_lines(i * 3)
+        })?;
+        sum += priority(badge)?;
+    }
+
+    Ok(sum)
+}
+
+/// Part 1, reading the input a line at a time
+pub fn part1_from(input: impl BufRead) -> Result<usize> {
+    let mut sum = 0;
+    parse::each_line(input, RUCKSACK, |line, number| {
+        let rucksack = parse::line(line, number, RUCKSACK, rucksack)?;
+        let item = in_both_compartments(rucksack).ok_or_else(|| {
+            ParseError::line(line, 1, IN_BOTH_COMPARTMENTS).offset_lines(number - 1)
+        })?;
+        sum += priority(item)?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// Part 2, reading the input a line at a time and keeping one group of rucksacks in memory
+pub fn part2_from(input: impl BufRead) -> Result<usize> {
+    let mut sum = 0;
+    let mut group = Vec::with_capacity(3);
+    let mut lines = 0;
+    parse::each_line(input, RUCKSACK, |line, number| {
+        group.push(parse::line(line, number, RUCKSACK, rucksack)?.to_owned());
+        lines = number;
+        if group.len() == 3 {
+            let badge = in_every_rucksack(&group).ok_or_else(|| {
+                ParseError::line(&group[0], 1, IN_EVERY_RUCKSACK).offset_lines(number - 3)
+            })?;
+            sum += priority(badge)?;
+            group.clear();
+        }
+        Ok(())
+    })?;
+    if !group.is_empty() {
+        return Err(ParseError::line("", 1, ANOTHER_RUCKSACK)
+            .offset_lines(lines)
+            .into());
+    }
+    Ok(sum)
+}
+
+const RUCKSACK: &str = "an even number of items, each a letter";
+const IN_BOTH_COMPARTMENTS: &str = "exactly one item type in both compartments";
+const IN_EVERY_RUCKSACK: &str = "a group of three rucksacks with exactly one item type in common";
+const ANOTHER_RUCKSACK: &str = "another rucksack, as the elves are in groups of three";
+
+/// The item type in both of the rucksack's compartments, if there is exactly one
+fn in_both_compartments(rucksack: &str) -> Option<char> {
+    let (first, second) = rucksack.split_at(rucksack.len() / 2);
+    in_every_rucksack(&[first, second])
+}
+
+/// The item type found in every one of `rucksacks`, if there is exactly one
+fn in_every_rucksack(rucksacks: &[impl AsRef<str>]) -> Option<char> {
+    let (first, rest) = rucksacks.split_first()?;
+    let mut in_all: HashSet<char> = first.as_ref().chars().collect();
+    for rucksack in rest {
+        let items: HashSet<char> = rucksack.as_ref().chars().collect();
+        in_all.retain(|c| items.contains(c));
+    }
+    match Vec::from_iter(in_all)[..] {
+        [item] => Some(item),
+        _ => None,
+    }
+}
+
+/// Gets the items in each rucksack, checking that they can be split into two compartments
+pub fn parse_rucksacks(input: &str) -> Result<Vec<&str>, ParseError> {
+    parse::lines(input, RUCKSACK, rucksack)
+}
+
+fn rucksack(i: &str) -> IResult<&str, &str> {
+    verify(alpha1, |items: &str| items.len().is_multiple_of(2))(i)
+}
+
+pub fn priority(c: char) -> Result<usize> {
+    const CHARS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    match CHARS.find(c) {
+        Some(i) => Ok(i + 1),
+        None => bail!("'{}' is not an item type, which are all letters", c),
+    }
+}
+
+/// The example input from the puzzle description
+const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        assert_eq!(part1(&parse_rucksacks(EXAMPLE).unwrap()).unwrap(), 157);
+    }
+
+    #[test]
+    fn test_part2_gives_correct_answer() {
+        assert_eq!(part2(&parse_rucksacks(EXAMPLE).unwrap()).unwrap(), 70);
+    }
+
+    #[test]
+    fn test_reports_rucksacks_that_are_not_letters() {
+        let err = parse_rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqz1GDLGLrsFMfFZSrLrFZsSL")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 11));
+    }
+
+    #[test]
+    fn test_reports_groups_without_one_common_item() {
+        let err = part2(&["abcd", "efgh", "ijkl"]).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(err.line, 1);
+        let rucksacks = parse_rucksacks(EXAMPLE).unwrap();
+        let err = part2(&rucksacks[..5]).unwrap_err();
+        assert_eq!(err.downcast::<ParseError>().unwrap().line, 6);
+    }
+
+    #[test]
+    fn test_only_letters_have_a_priority() {
+        assert_eq!(priority('a').unwrap(), 1);
+        assert_eq!(priority('Z').unwrap(), 52);
+        assert!(priority('1').is_err());
+        assert!(priority('é').is_err());
+    }
+
+    const LETTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    /// A group of three rucksacks that all contain the badge `letters[0]`, where rucksack `i`
+    /// has `letters[i + 1]` in both compartments, and `sizes[i]` other items in each.
+    /// Every other letter appears in only one compartment.
+    fn group(letters: &[char], sizes: [usize; 3]) -> Vec<String> {
+        let badge = letters[0];
+        (0..3)
+            .map(|i| {
+                let common = letters[i + 1];
+                // Each rucksack gets 16 letters of its own, 8 for each compartment
+                let own = &letters[4 + i * 16..4 + (i + 1) * 16];
+                let first: String = [common, badge]
+                    .into_iter()
+                    .chain(own[..sizes[i]].iter().copied())
+                    .collect();
+                let second: String = std::iter::once(common)
+                    .chain(own[8..9 + sizes[i]].iter().copied())
+                    .collect();
+                first + &second
+            })
+            .collect()
+    }
+
+    fn rucksacks() -> impl Strategy<Value = Vec<String>> {
+        let letters: Vec<char> = LETTERS.chars().collect();
+        let group = (Just(letters).prop_shuffle(), [0..8_usize, 0..8, 0..8])
+            .prop_map(|(letters, sizes)| group(&letters, sizes));
+        prop::collection::vec(group, 1..10).prop_map(|groups| groups.concat())
+    }
+
+    fn reference_priority(c: char) -> usize {
+        LETTERS.chars().position(|l| l == c).unwrap() + 1
+    }
+
+    proptest! {
+        #[test]
+        fn test_priorities_match_checking_every_item(rucksacks in rucksacks()) {
+            let input = rucksacks.join("\n");
+            let part1_sum: usize = rucksacks
+                .iter()
+                .map(|rucksack| {
+                    let (first, second) = rucksack.split_at(rucksack.len() / 2);
+                    let common = first.chars().find(|c| second.contains(*c)).unwrap();
+                    reference_priority(common)
+                })
+                .sum();
+            let part2_sum: usize = rucksacks
+                .chunks(3)
+                .map(|group| {
+                    let badge = group[0]
+                        .chars()
+                        .find(|c| group[1].contains(*c) && group[2].contains(*c))
+                        .unwrap();
+                    reference_priority(badge)
+                })
+                .sum();
+            let rucksacks = parse_rucksacks(&input).unwrap();
+            prop_assert_eq!(part1(&rucksacks).unwrap(), part1_sum);
+            prop_assert_eq!(part2(&rucksacks).unwrap(), part2_sum);
+        }
+    }
+}
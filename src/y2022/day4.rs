@@ -0,0 +1,195 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use anyhow::Result;
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{map, map_res},
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::{Example, Solution, Streaming};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("2"),
+        part2: Some("4"),
+    }];
+
+    type Input = Vec<Assignment>;
+
+    fn parse(input: &str) -> Result<Vec<Assignment>> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part1(input: &Vec<Assignment>) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Vec<Assignment>) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+impl Streaming for Day4 {
+    fn part1_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part1_from(input)?.into())
+    }
+
+    fn part2_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part2_from(input)?.into())
+    }
+}
+
+/// The number of pairs where one elf's sections include all of the other's
+pub fn part1(assignments: &[Assignment]) -> usize {
+    assignments
+        .iter()
+        .filter(|pair| fully_contains(pair))
+        .count()
+}
+
+/// The number of pairs that have any sections in common
+pub fn part2(assignments: &[Assignment]) -> usize {
+    assignments.iter().filter(|pair| overlaps(pair)).count()
+}
+
+/// Part 1, reading the input a line at a time
+pub fn part1_from(input: impl BufRead) -> Result<usize> {
+    read_count(input, fully_contains)
+}
+
+/// Part 2, reading the input a line at a time
+pub fn part2_from(input: impl BufRead) -> Result<usize> {
+    read_count(input, overlaps)
+}
+
+/// Whether one elf's sections include all of the other's
+fn fully_contains((r1, r2): &Assignment) -> bool {
+    (r1.contains(r2.start()) && r1.contains(r2.end()))
+        || (r2.contains(r1.start()) && r2.contains(r1.end()))
+}
+
+/// Whether the elves have any sections in common
+fn overlaps((r1, r2): &Assignment) -> bool {
+    r1.contains(r2.start())
+        || r1.contains(r2.end())
+        || r2.contains(r1.start())
+        || r2.contains(r1.end())
+}
+
+/// Counts the assignments read from `input` that match `predicate`
+fn read_count(input: impl BufRead, predicate: fn(&Assignment) -> bool) -> Result<usize> {
+    let mut count = 0;
+    parse::each_line(input, ASSIGNMENT, |line, number| {
+        if predicate(&parse::line(line, number, ASSIGNMENT, parse_line)?) {
+            count += 1;
+        }
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+fn number(digits: &str) -> IResult<&str, usize> {
+    map_res(digit1, |n: &str| n.parse::<usize>())(digits)
+}
+
+fn range_inclusive(input: &str) -> IResult<&str, RangeInclusive<usize>> {
+    map(separated_pair(number, tag("-"), number), |(start, end)| {
+        start..=end
+    })(input)
+}
+
+const ASSIGNMENT: &str = "a pair of ranges like '2-4,6-8'";
+
+/// The sections assigned to a pair of elves
+pub type Assignment = (RangeInclusive<usize>, RangeInclusive<usize>);
+
+pub fn parse_line(line: &str) -> IResult<&str, Assignment> {
+    separated_pair(range_inclusive, tag(","), range_inclusive)(line)
+}
+
+pub fn parse_lines(lines: &str) -> Result<Vec<Assignment>, ParseError> {
+    parse::lines(lines, ASSIGNMENT, parse_line)
+}
+
+/// The example input from the puzzle description
+const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        assert_eq!(part1(&parse_lines(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_part2_gives_correct_answer() {
+        assert_eq!(part2(&parse_lines(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_reports_where_a_range_is_malformed() {
+        let err = parse_lines("2-4,6-8\n2-3,4-\n5-7,7-9").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 7));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 7: expected a pair of ranges like '2-4,6-8', found the end of the line"
+        );
+    }
+
+    fn assignments() -> impl Strategy<Value = Vec<(usize, usize, usize, usize)>> {
+        let range = (1..100_usize, 0..20_usize).prop_map(|(start, len)| (start, start + len));
+        prop::collection::vec(
+            (range.clone(), range).prop_map(|((a, b), (c, d))| (a, b, c, d)),
+            1..50,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_counts_match_comparing_every_section(assignments in assignments()) {
+            let input = assignments
+                .iter()
+                .map(|(a, b, c, d)| format!("{}-{},{}-{}", a, b, c, d))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let sections = |start: usize, end: usize| (start..=end).collect::<HashSet<_>>();
+            let (mut contained, mut overlapping) = (0, 0);
+            for (a, b, c, d) in assignments.iter().copied() {
+                let (first, second) = (sections(a, b), sections(c, d));
+                if first.is_subset(&second) || second.is_subset(&first) {
+                    contained += 1;
+                }
+                if !first.is_disjoint(&second) {
+                    overlapping += 1;
+                }
+            }
+            let parsed = parse_lines(&input).unwrap();
+            prop_assert_eq!(part1(&parsed), contained);
+            prop_assert_eq!(part2(&parsed), overlapping);
+        }
+    }
+}
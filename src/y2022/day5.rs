@@ -0,0 +1,452 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Context, Result};
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{map, map_res},
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::{Example, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("CMZ"),
+        part2: Some("MCD"),
+    }];
+
+    type Input = GameState;
+
+    fn parse(input: &str) -> Result<GameState> {
+        GameState::try_from(input)
+    }
+
+    fn part1(input: &GameState) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &GameState) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+/// The crates on top of the stacks after moving crates one at a time
+pub fn part1(state: &GameState) -> Result<String> {
+    stacks_to_result(state.execute_part1()?)
+}
+
+/// The crates on top of the stacks after moving several crates at once
+pub fn part2(state: &GameState) -> Result<String> {
+    stacks_to_result(state.execute_part2()?)
+}
+
+pub fn stacks_to_result(mut stacks: Stacks) -> Result<String> {
+    let mut res = String::with_capacity(stacks.len());
+    for (i, stack) in stacks.iter_mut().enumerate() {
+        let Some(top) = stack.pop() else {
+            bail!(
+                "stack {} is empty at the end, so has no crate on top",
+                i + 1
+            );
+        };
+        res.push(top);
+    }
+    Ok(res)
+}
+
+/// The starting stacks of crates, and the moves to make
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameState {
+    pub stacks: Stacks,
+    pub moves: Vec<Move>,
+}
+
+pub type Stacks = Vec<Vec<char>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Move {
+    pub num: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.num, self.from, self.to)
+    }
+}
+
+impl GameState {
+    /// The stacks after making the moves one crate at a time
+    pub fn execute_part1(&self) -> Result<Stacks> {
+        let mut stacks = self.stacks.clone();
+        for (i, m) in self.moves.iter().enumerate() {
+            let (from, to) = check_move(&stacks, i, m)?;
+            for _ in 0..m.num {
+                if let Some(val) = stacks[from].pop() {
+                    stacks[to].push(val);
+                }
+            }
+        }
+        Ok(stacks)
+    }
+
+    /// The stacks after making the moves with all of a move's crates at once
+    pub fn execute_part2(&self) -> Result<Stacks> {
+        let mut stacks = self.stacks.clone();
+        for (i, m) in self.moves.iter().enumerate() {
+            let (from, to) = check_move(&stacks, i, m)?;
+            let split_idx = stacks[from].len() - m.num;
+            let mut to_move = stacks[from].split_off(split_idx);
+            stacks[to].append(&mut to_move);
+        }
+        Ok(stacks)
+    }
+}
+
+/// Checks that the `i`th move can be made with the stacks as they are now, and gets the
+/// indexes of the stacks that it moves crates between
+fn check_move(stacks: &Stacks, i: usize, m: &Move) -> Result<(usize, usize)> {
+    let stack_index = |n: usize| {
+        n.checked_sub(1)
+            .filter(|idx| *idx < stacks.len())
+            .with_context(|| {
+                format!(
+                    "move {} ('{}') uses stack {}, but there are only {} stacks",
+                    i + 1,
+                    m,
+                    n,
+                    stacks.len()
+                )
+            })
+    };
+    let (from, to) = (stack_index(m.from)?, stack_index(m.to)?);
+    if stacks[from].len() < m.num {
+        bail!(
+            "move {} ('{}') takes {} crates from stack {}, but it only has {}",
+            i + 1,
+            m,
+            m.num,
+            m.from,
+            stacks[from].len()
+        );
+    }
+    Ok((from, to))
+}
+
+impl TryFrom<&str> for GameState {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let Some((input_stacks, input_moves)) = value.split_once("\n\n") else {
+            return Err(ParseError::at(
+                value,
+                value.len(),
+                "a blank line between the stacks and the moves",
+            )
+            .into());
+        };
+        // The moves start after the stacks and the blank line
+        let moves_line = input_stacks.lines().count() + 1;
+        Ok(GameState {
+            stacks: parse_stacks(input_stacks)?,
+            moves: parse_moves(input_moves).map_err(|e| e.offset_lines(moves_line))?,
+        })
+    }
+}
+
+pub fn parse_stacks(input: &str) -> Result<Stacks, ParseError> {
+    let Some(first_line) = input.lines().next() else {
+        return Err(ParseError::at(
+            input,
+            0,
+            "a drawing of the stacks of crates",
+        ));
+    };
+    let num_stacks = (first_line.len() + 1) / 4;
+    let mut stacks: Vec<Vec<char>> = Vec::with_capacity(num_stacks);
+    for _ in 0..num_stacks {
+        stacks.push(Vec::new());
+    }
+
+    let mut line_offset = 0;
+    for line in input.split_inclusive('\n') {
+        for (i, c) in line.char_indices() {
+            if c.is_alphabetic() {
+                let stack_idx = i.div_ceil(4).checked_sub(1);
+                let Some(stack) = stack_idx.and_then(|idx| stacks.get_mut(idx)) else {
+                    return Err(ParseError::at(
+                        input,
+                        line_offset + i,
+                        format!("a crate in one of the {} stacks", num_stacks),
+                    ));
+                };
+                stack.push(c);
+            }
+        }
+        line_offset += line.len();
+    }
+
+    for stack in stacks.iter_mut() {
+        stack.reverse();
+    }
+
+    Ok(stacks)
+}
+
+fn number(digits: &str) -> IResult<&str, usize> {
+    map_res(digit1, |n: &str| n.parse::<usize>())(digits)
+}
+
+pub fn parse_move(input: &str) -> IResult<&str, Move> {
+    map(
+        tuple((
+            preceded(tag("move "), number),
+            preceded(tag(" from "), number),
+            preceded(tag(" to "), number),
+        )),
+        |(num, from, to)| Move { num, from, to },
+    )(input)
+}
+
+pub fn parse_moves(input: &str) -> Result<Vec<Move>, ParseError> {
+    parse::lines(input, "a move like 'move 1 from 2 to 1'", parse_move)
+}
+
+/// The example input from the puzzle description
+const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_can_parse_initial_stack_config() {
+        let (stacks_input, _) = EXAMPLE.split_once("\n\n").unwrap();
+        let stacks = parse_stacks(stacks_input).unwrap();
+        assert_eq!(stacks.len(), 3);
+        assert_eq!(stacks[0], vec!['Z', 'N']);
+        assert_eq!(stacks[1], vec!['M', 'C', 'D']);
+        assert_eq!(stacks[2], vec!['P']);
+    }
+
+    #[test]
+    fn test_can_parse_moves() {
+        let (_, moves_input) = EXAMPLE.split_once("\n\n").unwrap();
+        let expect = vec![
+            Move {
+                num: 1,
+                from: 2,
+                to: 1,
+            },
+            Move {
+                num: 3,
+                from: 1,
+                to: 3,
+            },
+            Move {
+                num: 2,
+                from: 2,
+                to: 1,
+            },
+            Move {
+                num: 1,
+                from: 1,
+                to: 2,
+            },
+        ];
+        assert_eq!(parse_moves(moves_input).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        let state = GameState::try_from(EXAMPLE).unwrap();
+        assert_eq!(part1(&state).unwrap(), "CMZ".to_string());
+    }
+
+    #[test]
+    fn test_part2_gives_correct_answer() {
+        let state = GameState::try_from(EXAMPLE).unwrap();
+        assert_eq!(part2(&state).unwrap(), "MCD".to_string());
+    }
+
+    #[test]
+    fn test_reports_malformed_moves_by_their_line_in_the_input() {
+        let input = EXAMPLE.replace("move 2 from 2 to 1", "move 2 from two to 1");
+        let err = GameState::try_from(input.as_str()).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (8, 13));
+        assert_eq!(err.text, "move 2 from two to 1");
+    }
+
+    #[test]
+    fn test_reports_missing_moves() {
+        let (stacks, _) = EXAMPLE.split_once("\n\n").unwrap();
+        let err = GameState::try_from(stacks).unwrap_err();
+        assert_eq!(
+            err.downcast::<ParseError>().unwrap().expected,
+            "a blank line between the stacks and the moves"
+        );
+    }
+
+    #[test]
+    fn test_reports_moves_from_missing_stacks() {
+        let input = EXAMPLE.replace("move 1 from 2 to 1", "move 1 from 4 to 1");
+        let err = part1(&GameState::try_from(input.as_str()).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "move 1 ('move 1 from 4 to 1') uses stack 4, but there are only 3 stacks"
+        );
+        let input = EXAMPLE.replace("move 1 from 2 to 1", "move 1 from 0 to 1");
+        assert!(part2(&GameState::try_from(input.as_str()).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_reports_moving_more_crates_than_a_stack_has() {
+        let input = EXAMPLE.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let state = GameState::try_from(input.as_str()).unwrap();
+        let err = part2(&state).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "move 2 ('move 4 from 1 to 3') takes 4 crates from stack 1, but it only has 3"
+        );
+        assert!(part1(&state).is_err());
+    }
+
+    #[test]
+    fn test_reports_empty_stacks_at_the_end() {
+        let state = GameState {
+            stacks: vec![vec!['A'], vec![]],
+            moves: vec![Move {
+                num: 1,
+                from: 1,
+                to: 2,
+            }],
+        };
+        let err = part1(&state).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "stack 1 is empty at the end, so has no crate on top"
+        );
+    }
+
+    #[test]
+    fn test_reports_crates_outside_the_stacks() {
+        let input = "A  \n[B]\n 1 \n\nmove 1 from 1 to 1";
+        let err = GameState::try_from(input).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    /// Draws the stacks the way the puzzle does, with the bottom crate of each stack first
+    fn draw(stacks: &[Vec<char>]) -> String {
+        let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut lines: Vec<String> = (0..height)
+            .rev()
+            .map(|level| {
+                stacks
+                    .iter()
+                    .map(|stack| match stack.get(level) {
+                        Some(c) => format!("[{}]", c),
+                        None => "   ".to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        lines.push(
+            (1..=stacks.len())
+                .map(|n| format!(" {} ", n))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        lines.join("\n")
+    }
+
+    /// Stacks of crates and moves that only ever take crates that are there
+    fn puzzle() -> impl Strategy<Value = (Vec<Vec<char>>, Vec<Move>)> {
+        let stacks = prop::collection::vec(
+            prop::collection::vec(prop::char::range('A', 'Z'), 0..8),
+            1..10,
+        );
+        let moves = prop::collection::vec((0..100_usize, 0..100_usize, 0..100_usize), 1..30);
+        (stacks, moves).prop_map(|(stacks, moves)| {
+            let mut sizes: Vec<usize> = stacks.iter().map(Vec::len).collect();
+            let moves = moves
+                .into_iter()
+                .filter_map(|(num, from, to)| {
+                    let from = from % sizes.len();
+                    let to = to % sizes.len();
+                    if sizes[from] == 0 {
+                        return None;
+                    }
+                    let num = 1 + num % sizes[from];
+                    sizes[from] -= num;
+                    sizes[to] += num;
+                    Some(Move {
+                        num,
+                        from: from + 1,
+                        to: to + 1,
+                    })
+                })
+                .collect();
+            (stacks, moves)
+        })
+    }
+
+    /// Moves crates one at a time, using a temporary stack for part 2 so that they keep
+    /// their order
+    fn reference(mut stacks: Vec<Vec<char>>, moves: &[Move], keep_order: bool) -> Option<String> {
+        for m in moves {
+            let mut held = Vec::new();
+            for _ in 0..m.num {
+                let c = stacks[m.from - 1].pop().unwrap();
+                if keep_order {
+                    held.push(c);
+                } else {
+                    stacks[m.to - 1].push(c);
+                }
+            }
+            held.reverse();
+            stacks[m.to - 1].extend(held);
+        }
+        stacks.iter().map(|stack| stack.last().copied()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_top_crates_match_moving_one_at_a_time((stacks, moves) in puzzle()) {
+            prop_assume!(!moves.is_empty() && stacks.iter().any(|stack| !stack.is_empty()));
+            let input = format!(
+                "{}\n\n{}\n",
+                draw(&stacks),
+                moves.iter().map(Move::to_string).collect::<Vec<_>>().join("\n")
+            );
+            let state = GameState::try_from(input.as_str()).unwrap();
+            prop_assert_eq!(&state, &GameState { stacks: stacks.clone(), moves: moves.clone() });
+            prop_assert_eq!(part1(&state).ok(), reference(stacks.clone(), &moves, false));
+            prop_assert_eq!(part2(&state).ok(), reference(stacks, &moves, true));
+        }
+    }
+}
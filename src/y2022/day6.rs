@@ -0,0 +1,165 @@
+use std::collections::{HashSet, VecDeque};
+use std::io::BufRead;
+
+use anyhow::{bail, Result};
+
+use crate::answer::Answer;
+use crate::parse;
+use crate::solution::{Example, Solution, Streaming};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            part1: Some("7"),
+            part2: Some("19"),
+        },
+        Example {
+            input: "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            part1: Some("5"),
+            part2: Some("23"),
+        },
+        Example {
+            input: "nppdvjthqldpwncqszvftbrmjlhg",
+            part1: Some("6"),
+            part2: Some("23"),
+        },
+        Example {
+            input: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            part1: Some("10"),
+            part2: Some("29"),
+        },
+        Example {
+            input: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+            part1: Some("11"),
+            part2: Some("26"),
+        },
+    ];
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &String) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &String) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+impl Streaming for Day6 {
+    fn part1_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part1_from(input)?.into())
+    }
+
+    fn part2_from(input: &mut dyn BufRead) -> Result<Answer> {
+        Ok(part2_from(input)?.into())
+    }
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    solve(input.chars().map(Ok), 4)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    solve(input.chars().map(Ok), 14)
+}
+
+/// Part 1, reading the input a character at a time
+pub fn part1_from(input: impl BufRead) -> Result<usize> {
+    solve(parse::chars(input), 4)
+}
+
+/// Part 2, reading the input a character at a time
+pub fn part2_from(input: impl BufRead) -> Result<usize> {
+    solve(parse::chars(input), 14)
+}
+
+/// Find the (1-indexed) position of the end of the first sequence of `find_len` contiguous
+/// distinct characters in `input`
+fn solve(input: impl Iterator<Item = Result<char>>, find_len: usize) -> Result<usize> {
+    let mut deq = VecDeque::with_capacity(find_len);
+    let mut diff_chars = HashSet::with_capacity(find_len);
+
+    for (i, c) in input.enumerate() {
+        deq.push_back(c?);
+        if deq.len() == find_len {
+            // convert the characters from the Deque to a Set
+            for recent_c in deq.iter() {
+                diff_chars.insert(*recent_c);
+            }
+            // Check if we have expected number of distinct characters
+            if diff_chars.len() == find_len {
+                return Ok(i + 1);
+            }
+            // Didn't have right number of distinct letters
+            diff_chars.clear();
+            deq.pop_front();
+        }
+    }
+    bail!(
+        "did not find {} different characters adjacent to each other",
+        find_len
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    pub fn test_part_1_gives_correct_answer() {
+        assert_eq!(part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), 7);
+        assert_eq!(part1("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 5);
+        assert_eq!(part1("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 6);
+        assert_eq!(part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), 10);
+        assert_eq!(part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), 11);
+    }
+
+    #[test]
+    pub fn test_part_2_gives_correct_answer() {
+        assert_eq!(part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), 19);
+        assert_eq!(part2("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 23);
+        assert_eq!(part2("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 23);
+        assert_eq!(part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), 29);
+        assert_eq!(part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), 26);
+    }
+
+    #[test]
+    fn test_reports_input_without_a_marker() {
+        assert_eq!(
+            part1("abcabcabc").unwrap_err().to_string(),
+            "did not find 4 different characters adjacent to each other"
+        );
+        assert!(part2("").is_err());
+    }
+
+    /// Finds the marker by comparing every pair of characters in each window
+    fn reference(input: &str, len: usize) -> Option<usize> {
+        let chars: Vec<char> = input.chars().collect();
+        (len..=chars.len()).find(|&end| {
+            let window = &chars[end - len..end];
+            (0..len).all(|i| (i + 1..len).all(|j| window[i] != window[j]))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_markers_match_comparing_every_pair(input in "[a-p]{0,200}") {
+            prop_assert_eq!(part1(&input).ok(), reference(&input, 4));
+            prop_assert_eq!(part2(&input).ok(), reference(&input, 14));
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use std::{collections::HashMap, fmt::Debug};
+
+use anyhow::{bail, Context, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, line_ending, not_line_ending},
+    combinator::{map, map_res, opt},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::{Example, Solution};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("95437"),
+        part2: Some("24933642"),
+    }];
+
+    type Input = State;
+
+    fn parse(input: &str) -> Result<State> {
+        State::try_from(input)
+    }
+
+    fn part1(input: &State) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &State) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+/// The total size of the directories of at most 100000 bytes
+pub fn part1(state: &State) -> usize {
+    state.sizes.values().filter(|&size| *size <= 100000).sum()
+}
+
+/// The size of the smallest directory that frees up enough space when deleted
+pub fn part2(state: &State) -> Result<usize> {
+    const TOTAL_SPACE: usize = 70000000;
+    const NEED_FREE: usize = 30000000;
+
+    let Some(free) = TOTAL_SPACE.checked_sub(state.used_space()) else {
+        bail!(
+            "the files use {} bytes, which is more than the disk's {}",
+            state.used_space(),
+            TOTAL_SPACE
+        );
+    };
+    let Some(need) = NEED_FREE.checked_sub(free).filter(|need| *need > 0) else {
+        bail!("{} bytes are already free, so nothing needs deleting", free);
+    };
+    let smallest = state
+        .sizes
+        .values()
+        .filter(|&size| *size >= need)
+        .min()
+        .with_context(|| format!("no directory is large enough to free {} bytes", need))?;
+
+    Ok(*smallest)
+}
+
+/// The size of every directory, found by following a terminal session
+#[derive(Debug)]
+pub struct State {
+    working_dir: WorkingDir,
+    pub sizes: HashMap<String, usize>,
+}
+
+impl State {
+    pub fn from_commands(cmds: Vec<Command>) -> Result<Self> {
+        let mut state = Self {
+            working_dir: WorkingDir::new(),
+            sizes: HashMap::new(),
+        };
+        state.apply_all(cmds)?;
+        Ok(state)
+    }
+
+    fn apply(&mut self, cmd: Command) -> Result<()> {
+        match cmd {
+            Command::CdUp => self.working_dir.cd_up(),
+            Command::CdInto(dir) => self.working_dir.cd_into(dir.as_ref()),
+            Command::Ls(DirListing(entries)) => {
+                let dir_size = entries
+                    .iter()
+                    .map(|entry| match entry {
+                        DirEntry::File(_, size) => *size,
+                        DirEntry::Dir(_) => 0,
+                    })
+                    .try_fold(0_usize, |total, size| total.checked_add(size));
+                for path in self.working_dir.tree_paths() {
+                    let size = self.sizes.entry(path).or_insert(0);
+                    *size = dir_size
+                        .and_then(|dir_size| size.checked_add(dir_size))
+                        .with_context(|| {
+                            format!("the size of {:?} is too large", self.working_dir)
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_all(&mut self, cmds: Vec<Command>) -> Result<()> {
+        for cmd in cmds {
+            self.apply(cmd)?;
+        }
+        Ok(())
+    }
+
+    pub fn used_space(&self) -> usize {
+        *self.sizes.get("/").unwrap_or(&0)
+    }
+}
+
+impl TryFrom<&str> for State {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::from_commands(parse_command_list(value)?)
+    }
+}
+
+struct WorkingDir(Vec<String>);
+
+impl WorkingDir {
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn cd_into(&mut self, dir: &str) {
+        self.0.push(dir.to_owned());
+    }
+
+    fn cd_up(&mut self) {
+        self.0.pop();
+    }
+
+    /// Gets current path, plus all of its "parent" paths
+    fn tree_paths(&self) -> Vec<String> {
+        let mut paths = Vec::with_capacity(self.0.len() + 1);
+        paths.push("/".into());
+
+        if self.0.is_empty() {
+            return paths;
+        }
+        for i in 1..=self.0.len() {
+            paths.push(self.0[0..i].join("/"));
+        }
+        paths
+    }
+}
+
+impl Debug for WorkingDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "/");
+        }
+        let dirs = self.0.join("/");
+        write!(f, "/{}", dirs)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    CdUp,
+    CdInto(String),
+    Ls(DirListing),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DirEntry {
+    File(String, usize),
+    Dir(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DirListing(pub Vec<DirEntry>);
+
+pub fn command(i: &str) -> IResult<&str, Command> {
+    // "$ cd .."
+    let cd_up = map(tag("$ cd .."), |_| Command::CdUp);
+    // "$ cd fmfnpm"
+    let cd_into = map(preceded(tag("$ cd "), alpha1), |name: &str| {
+        Command::CdInto(name.into())
+    });
+    // "$ ls"
+    let ls = map(
+        preceded(tuple((tag("$ ls"), line_ending)), dir_listing),
+        Command::Ls,
+    );
+    alt((cd_up, cd_into, ls))(i)
+}
+
+pub fn parse_command_list(i: &str) -> Result<Vec<Command>, ParseError> {
+    // Require that the list of commands always starts by changing into the root directory
+    let Ok((rest, _)) = cd_root(i) else {
+        return Err(ParseError::at(i, 0, "'$ cd /' as the first command"));
+    };
+    let commands = separated_list1(line_ending, command);
+    match terminated(commands, opt(line_ending))(rest) {
+        Ok(("", commands)) => Ok(commands),
+        Ok((rest, _)) => Err(ParseError::at(
+            i,
+            i.len() - rest.len(),
+            "a command or a directory entry",
+        )),
+        Err(e) => Err(ParseError::at(
+            i,
+            i.len() - parse::remaining(&e).unwrap_or_default().len(),
+            "a command like '$ cd a' or '$ ls'",
+        )),
+    }
+}
+
+fn cd_root(i: &str) -> IResult<&str, &str> {
+    terminated(tag("$ cd /"), line_ending)(i)
+}
+
+fn number(i: &str) -> IResult<&str, usize> {
+    map_res(digit1, |n: &str| n.parse::<usize>())(i)
+}
+
+pub fn dir_entry(i: &str) -> IResult<&str, DirEntry> {
+    // "13445 b.txt"
+    let file = map(
+        separated_pair(number, tag(" "), not_line_ending),
+        |(size, name)| DirEntry::File(name.to_owned(), size),
+    );
+    // "dir dassfsdf"
+    let dir = map(preceded(tag("dir "), alpha1), |name: &str| {
+        DirEntry::Dir(name.to_owned())
+    });
+    alt((file, dir))(i)
+}
+
+pub fn dir_listing(i: &str) -> IResult<&str, DirListing> {
+    map(separated_list1(line_ending, dir_entry), |entries| {
+        DirListing(entries)
+    })(i)
+}
+
+/// The example input from the puzzle description
+const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_can_parse_dir_listing() {
+        let input = "14848514 fwdvgnqp.txt\n8504156 c.dat\ndir fmfnpm";
+        let expect = DirListing(vec![
+            DirEntry::File("fwdvgnqp.txt".into(), 14848514),
+            DirEntry::File("c.dat".into(), 8504156),
+            DirEntry::Dir("fmfnpm".into()),
+        ]);
+        let (rest, listing) = dir_listing(input).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(listing, expect);
+    }
+
+    #[test]
+    fn test_can_parse_input() {
+        let expect = vec![
+            Command::Ls(DirListing(vec![
+                DirEntry::Dir("a".into()),
+                DirEntry::File("b.txt".into(), 14848514),
+                DirEntry::File("c.dat".into(), 8504156),
+                DirEntry::Dir("d".into()),
+            ])),
+            Command::CdInto("a".into()),
+            Command::Ls(DirListing(vec![
+                DirEntry::Dir("e".into()),
+                DirEntry::File("f".into(), 29116),
+                DirEntry::File("g".into(), 2557),
+                DirEntry::File("h.lst".into(), 62596),
+            ])),
+            Command::CdInto("e".into()),
+            Command::Ls(DirListing(vec![DirEntry::File("i".into(), 584)])),
+            Command::CdUp,
+            Command::CdUp,
+            Command::CdInto("d".into()),
+            Command::Ls(DirListing(vec![
+                DirEntry::File("j".into(), 4060174),
+                DirEntry::File("d.log".into(), 8033020),
+                DirEntry::File("d.ext".into(), 5626152),
+                DirEntry::File("k".into(), 7214296),
+            ])),
+        ];
+        let commands = parse_command_list(EXAMPLE).unwrap();
+        assert_eq!(commands, expect);
+    }
+
+    #[test]
+    fn test_part_1_gives_correct_answer() {
+        assert_eq!(part1(&State::try_from(EXAMPLE).unwrap()), 95437);
+    }
+
+    #[test]
+    fn test_part_2_gives_correct_answer() {
+        assert_eq!(part2(&State::try_from(EXAMPLE).unwrap()).unwrap(), 24933642);
+    }
+
+    #[test]
+    fn test_reports_the_line_that_is_not_a_command() {
+        let input = EXAMPLE.replace("$ cd e", "$ cd e/f");
+        let err = parse_command_list(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (13, 7));
+        assert_eq!(err.text, "$ cd e/f");
+
+        let err = parse_command_list("$ ls\ndir a").unwrap_err();
+        assert_eq!(err.expected, "'$ cd /' as the first command");
+    }
+
+    #[test]
+    fn test_reports_disks_that_cannot_be_freed_up() {
+        let err = part2(&State::try_from("$ cd /\n$ ls\n80000000 a").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the files use 80000000 bytes, which is more than the disk's 70000000"
+        );
+        let err = part2(&State::try_from("$ cd /\n$ ls\n100 a").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "69999900 bytes are already free, so nothing needs deleting"
+        );
+        let input = format!("$ cd /\n$ ls\n{} a\n1 b", usize::MAX);
+        assert!(State::try_from(input.as_str()).is_err());
+    }
+
+    /// A directory, holding the sizes of its files and its subdirectories
+    #[derive(Debug, Clone)]
+    struct Dir {
+        files: Vec<usize>,
+        dirs: Vec<Dir>,
+    }
+
+    impl Dir {
+        fn size(&self) -> usize {
+            self.files.iter().sum::<usize>() + self.dirs.iter().map(Dir::size).sum::<usize>()
+        }
+
+        /// The sizes of this directory and every directory below it
+        fn all_sizes(&self) -> Vec<usize> {
+            std::iter::once(self.size())
+                .chain(self.dirs.iter().flat_map(Dir::all_sizes))
+                .collect()
+        }
+
+        /// Writes the commands that list this directory and then everything below it
+        fn session(&self, out: &mut Vec<String>) {
+            out.push("$ ls".into());
+            for i in 0..self.dirs.len() {
+                out.push(format!("dir {}", dir_name(i)));
+            }
+            for (i, size) in self.files.iter().enumerate() {
+                out.push(format!("{} file{}.txt", size, i));
+            }
+            for (i, dir) in self.dirs.iter().enumerate() {
+                out.push(format!("$ cd {}", dir_name(i)));
+                dir.session(out);
+                out.push("$ cd ..".into());
+            }
+        }
+    }
+
+    fn dir_name(i: usize) -> String {
+        ((b'a' + (i % 26) as u8) as char)
+            .to_string()
+            .repeat(i / 26 + 1)
+    }
+
+    /// Directory trees where every directory has something in it, as `ls` always lists
+    /// at least one entry
+    fn tree() -> impl Strategy<Value = Dir> {
+        let size = prop_oneof![1..50_000_usize, 1..20_000_000_usize];
+        let files = prop::collection::vec(size.clone(), 1..5);
+        let leaf = files.prop_map(|files| Dir {
+            files,
+            dirs: Vec::new(),
+        });
+        leaf.prop_recursive(4, 40, 4, move |inner| {
+            (
+                prop::collection::vec(size.clone(), 0..4),
+                prop::collection::vec(inner, 1..4),
+            )
+                .prop_map(|(files, dirs)| Dir { files, dirs })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_sizes_match_walking_the_tree(root in tree()) {
+            let mut session = vec!["$ cd /".to_string()];
+            root.session(&mut session);
+            let input = session.join("\n");
+
+            let sizes = root.all_sizes();
+            let small_total: usize = sizes.iter().filter(|size| **size <= 100000).sum();
+            let to_delete = 70000000_usize
+                .checked_sub(root.size())
+                .filter(|free| *free < 30000000)
+                .and_then(|free| sizes.iter().filter(|size| **size >= 30000000 - free).min().copied());
+            let state = State::try_from(input.as_str()).unwrap();
+            prop_assert_eq!(part1(&state), small_total);
+            prop_assert_eq!(part2(&state).ok(), to_delete);
+        }
+    }
+}
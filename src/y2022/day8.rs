@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use nom::character::complete::digit1;
+
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::{Example, Solution};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u16 = 2022;
+    const DAY: u16 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("21"),
+        part2: Some("8"),
+    }];
+
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Grid> {
+        Ok(build_grid(input)?)
+    }
+
+    fn part1(input: &Grid) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Grid) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+/// The number of trees that can be seen from outside the grid
+pub fn part1(grid: &Grid) -> usize {
+    let mut visibilities = visibilities(grid, false);
+    let mut heights = grid.clone();
+
+    for row in heights.iter() {
+        process_row_part1(row.iter(), &mut visibilities);
+        process_row_part1(row.iter().rev(), &mut visibilities);
+    }
+
+    heights.reverse();
+    let heights = transpose(heights);
+
+    for row in heights.iter() {
+        process_row_part1(row.iter(), &mut visibilities);
+        process_row_part1(row.iter().rev(), &mut visibilities);
+    }
+
+    visibilities.values().filter(|v| *v == &true).count()
+}
+
+/// The highest scenic score of any tree
+pub fn part2(grid: &Grid) -> Result<usize> {
+    let mut visibilities = visibilities(grid, 1_usize);
+    let mut heights = grid.clone();
+
+    for row in heights.iter() {
+        process_row_part2(row.iter(), &mut visibilities);
+        process_row_part2(row.iter().rev(), &mut visibilities);
+    }
+
+    heights.reverse();
+    let heights = transpose(heights);
+
+    for row in heights.iter() {
+        process_row_part2(row.iter(), &mut visibilities);
+        process_row_part2(row.iter().rev(), &mut visibilities);
+    }
+
+    let max_scenic_score = *visibilities
+        .values()
+        .max()
+        .context("there are no trees to give a scenic score")?;
+
+    Ok(max_scenic_score)
+}
+
+/// The height of each tree, along with its `(x, y)` position, in rows
+pub type Grid = Vec<Vec<((usize, usize), usize)>>;
+
+pub type Visibilities<T> = HashMap<(usize, usize), T>;
+
+/// Parses the tree heights, which are given as a rectangle of digits
+pub fn build_grid(input: &str) -> Result<Grid, ParseError> {
+    let rows = parse::lines(input, "a row of tree heights from 0 to 9", digit1)?;
+    let width = rows[0].len();
+    if let Some(y) = rows.iter().position(|row| row.len() != width) {
+        return Err(ParseError::line(
+            input,
+            y + 1,
+            format!("a row of {} trees, the same as the first row", width),
+        ));
+    }
+
+    let heights = rows
+        .into_iter()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .filter_map(|c| c.to_digit(10))
+                .enumerate()
+                .map(|(x, height)| ((x, y), height as usize))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    Ok(heights)
+}
+
+/// A visibility for every tree in the grid, starting at `default_visibility`
+fn visibilities<T: Copy>(grid: &Grid, default_visibility: T) -> Visibilities<T> {
+    grid.iter()
+        .flatten()
+        .map(|(position, _)| (*position, default_visibility))
+        .collect()
+}
+
+fn process_row_part1<'a, I>(row: I, visibilities: &mut HashMap<(usize, usize), bool>)
+where
+    I: Iterator<Item = &'a ((usize, usize), usize)>,
+{
+    let mut tallest = None;
+
+    for ((x, y), height) in row {
+        if tallest.is_none() {
+            visibilities.insert((*x, *y), true);
+        }
+        if let Some(tallest) = tallest {
+            if *height > tallest {
+                visibilities.insert((*x, *y), true);
+            }
+        }
+
+        tallest = tallest.map_or(Some(*height), |t| {
+            if t < *height {
+                Some(*height)
+            } else {
+                Some(t)
+            }
+        });
+    }
+}
+
+fn process_row_part2<'a, I>(row: I, visibilities: &mut HashMap<(usize, usize), usize>)
+where
+    I: Iterator<Item = &'a ((usize, usize), usize)>,
+{
+    let mut prev_heights: Vec<usize> = Vec::new();
+
+    for (i, ((x, y), height)) in row.enumerate() {
+        if prev_heights.is_empty() {
+            visibilities.entry((*x, *y)).and_modify(|vis| *vis *= 0);
+        } else {
+            // Find the index of the last tree we saw that was this tall or taller
+            let prev_idx = prev_heights
+                .iter()
+                .enumerate()
+                .filter(|(_, &v)| v >= *height)
+                .map(|(j, _)| j)
+                .next_back();
+            // Calculate the distance to that last tree
+            let run_length = match prev_idx {
+                None => prev_heights.len(),
+                Some(j) => i - j,
+            };
+            visibilities
+                .entry((*x, *y))
+                .and_modify(|vis| *vis *= run_length);
+        }
+        prev_heights.push(*height);
+    }
+}
+
+/// Turns rows into columns. Rows that are shorter than the first are treated as if they
+/// were cut off at the end.
+fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let len = v.first().map_or(0, Vec::len);
+    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
+    (0..len)
+        .map(|_| {
+            iters
+                .iter_mut()
+                .filter_map(|n| n.next())
+                .collect::<Vec<T>>()
+        })
+        .collect()
+}
+
+/// The example input from the puzzle description
+const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390";
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        let visible_trees = part1(&build_grid(EXAMPLE).unwrap());
+        assert_eq!(visible_trees, 21);
+    }
+
+    #[test]
+    fn test_part2_gives_correct_answer() {
+        let visible_trees = part2(&build_grid(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(visible_trees, 8);
+    }
+
+    #[test]
+    fn test_reports_rows_of_different_widths() {
+        let err = build_grid("30373\n2551\n65332").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.expected, "a row of 5 trees, the same as the first row");
+    }
+
+    #[test]
+    fn test_reports_rows_that_are_not_heights() {
+        assert!(build_grid("").is_err());
+        assert!(build_grid("303 3\n25512").is_err());
+        assert!(part2(&vec![]).is_err());
+        assert_eq!(transpose::<u8>(vec![]), Vec::<Vec<u8>>::new());
+    }
+
+    /// Counts visible trees and scenic scores by looking out from every tree in turn
+    fn reference(grid: &[Vec<u8>]) -> (usize, usize) {
+        let (height, width) = (grid.len(), grid[0].len());
+        let (mut visible, mut best_score) = (0, 0);
+        for y in 0..height {
+            for x in 0..width {
+                let tree = grid[y][x];
+                let lines_of_sight: [Vec<u8>; 4] = [
+                    (0..x).rev().map(|x| grid[y][x]).collect(),
+                    (x + 1..width).map(|x| grid[y][x]).collect(),
+                    (0..y).rev().map(|y| grid[y][x]).collect(),
+                    (y + 1..height).map(|y| grid[y][x]).collect(),
+                ];
+                if lines_of_sight
+                    .iter()
+                    .any(|line| line.iter().all(|other| *other < tree))
+                {
+                    visible += 1;
+                }
+                let score = lines_of_sight
+                    .iter()
+                    .map(|line| match line.iter().position(|other| *other >= tree) {
+                        Some(blocked) => blocked + 1,
+                        None => line.len(),
+                    })
+                    .product();
+                best_score = best_score.max(score);
+            }
+        }
+        (visible, best_score)
+    }
+
+    fn grid() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1..12_usize, 1..12_usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop::collection::vec(0..10_u8, width), height)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_answers_match_looking_from_every_tree(grid in grid()) {
+            let input = grid
+                .iter()
+                .map(|row| row.iter().map(|h| h.to_string()).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let (visible, best_score) = reference(&grid);
+            let parsed = build_grid(&input).unwrap();
+            prop_assert_eq!(part1(&parsed), visible);
+            prop_assert_eq!(part2(&parsed).unwrap(), best_score);
+        }
+    }
+}
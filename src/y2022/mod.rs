@@ -0,0 +1,10 @@
+//! Solutions to the [Advent of Code 2022](https://adventofcode.com/2022) puzzles
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;